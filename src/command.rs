@@ -0,0 +1,107 @@
+use crate::config::Config;
+use crate::error::SnipsError;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a running command is polled for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Whether the arguments of `argv` are covered by the `commands` allowlist
+/// in `config`.
+///
+/// Entries are split like command lines, and an entry allows the exact
+/// arguments and any arguments that extend them, matching whole arguments
+/// only.
+fn is_allowed(argv: &[String], config: &Config) -> bool {
+    config.commands.iter().any(|allowed| {
+        shell_words::split(allowed)
+            .is_ok_and(|prefix| !prefix.is_empty() && argv.starts_with(&prefix))
+    })
+}
+
+/// Run an allowlisted `command` in `dir` and return its standard output.
+///
+/// The command line is split into arguments with shell quoting rules and run
+/// directly, without a shell, so operators, variables and globs are passed
+/// through literally. The command is killed once it runs longer than the configured timeout.
+/// Line endings are normalised to `\n` and trailing newlines are removed.
+pub(crate) fn run(command: &str, dir: &Path, config: &Config) -> Result<String, SnipsError> {
+    let argv = shell_words::split(command).map_err(|err| SnipsError::InvalidCommand {
+        command: command.to_string(),
+        reason: err.to_string(),
+    })?;
+    let Some((program, args)) = argv.split_first() else {
+        return Err(SnipsError::InvalidCommand {
+            command: command.to_string(),
+            reason: "no program given".to_string(),
+        });
+    };
+    if !is_allowed(&argv, config) {
+        return Err(SnipsError::CommandNotAllowed(command.to_string()));
+    }
+    // The parent of a bare relative file name is empty.
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    let mut child = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|source| SnipsError::CommandSpawnFailed {
+            command: command.to_string(),
+            source,
+        })?;
+    // Drain both pipes concurrently so a chatty command can't block on a full pipe.
+    let mut stdout = child.stdout.take().unwrap();
+    let mut stderr = child.stderr.take().unwrap();
+    let stdout = thread::spawn(move || {
+        let mut buf = Vec::new();
+        stdout.read_to_end(&mut buf).map(|_| buf)
+    });
+    let stderr = thread::spawn(move || {
+        let mut buf = Vec::new();
+        stderr.read_to_end(&mut buf).map(|_| buf)
+    });
+
+    let deadline = Instant::now() + Duration::from_secs(config.command_timeout);
+    let spawn_failed = |source| SnipsError::CommandSpawnFailed {
+        command: command.to_string(),
+        source,
+    };
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(spawn_failed)? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            // The command is abandoned either way, so failures here don't matter.
+            child.kill().ok();
+            child.wait().ok();
+            return Err(SnipsError::CommandTimedOut {
+                command: command.to_string(),
+                seconds: config.command_timeout,
+            });
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    let stdout = stdout.join().unwrap().map_err(spawn_failed)?;
+    let stderr = stderr.join().unwrap().map_err(spawn_failed)?;
+    if !status.success() {
+        return Err(SnipsError::CommandFailed {
+            command: command.to_string(),
+            status: status.to_string(),
+            stderr: String::from_utf8_lossy(&stderr).trim_end().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&stdout)
+        .replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string())
+}
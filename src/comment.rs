@@ -0,0 +1,154 @@
+use std::path::Path;
+
+/// Line and block comment delimiters for a language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CommentSyntax {
+    /// Prefixes that start a comment running to the end of the line.
+    pub line: &'static [&'static str],
+    /// Opening and closing delimiters of block comments.
+    pub block: &'static [(&'static str, &'static str)],
+}
+
+impl CommentSyntax {
+    /// Write `text` as a comment on its own line, preferring a line comment.
+    pub fn comment(&self, text: &str) -> String {
+        match (self.line.first(), self.block.first()) {
+            (Some(open), _) => format!("{open} {text}"),
+            (None, Some((open, close))) => format!("{open} {text} {close}"),
+            (None, None) => text.to_string(),
+        }
+    }
+}
+
+/// `//` and `/* */`, shared by the C family.
+const C_STYLE: CommentSyntax = CommentSyntax {
+    line: &["//"],
+    block: &[("/*", "*/")],
+};
+
+/// `#` line comments only.
+const HASH: CommentSyntax = CommentSyntax {
+    line: &["#"],
+    block: &[],
+};
+
+/// `<!-- -->` block comments only.
+const MARKUP: CommentSyntax = CommentSyntax {
+    line: &[],
+    block: &[("<!--", "-->")],
+};
+
+/// `;` line comments only.
+const SEMICOLON: CommentSyntax = CommentSyntax {
+    line: &[";"],
+    block: &[],
+};
+
+/// `%` line comments only.
+const PERCENT: CommentSyntax = CommentSyntax {
+    line: &["%"],
+    block: &[],
+};
+
+/// Syntax used when the language is unknown: every common comment style.
+const FALLBACK: CommentSyntax = CommentSyntax {
+    line: &["//", "#", "--", ";", "%"],
+    block: &[("/*", "*/"), ("<!--", "-->")],
+};
+
+/// Look up the comment syntax for a Linguist language name.
+fn for_language(language: &str) -> Option<CommentSyntax> {
+    let syntax = match language {
+        "C" | "C++" | "C#" | "Objective-C" | "Java" | "JavaScript" | "TypeScript" | "TSX"
+        | "Go" | "Rust" | "Swift" | "Kotlin" | "Scala" | "Dart" | "Groovy" | "SCSS" | "Less"
+        | "Protocol Buffer" | "GraphQL" | "JSON5" => C_STYLE,
+        "Zig" => CommentSyntax {
+            line: &["//"],
+            block: &[],
+        },
+        "CSS" => CommentSyntax {
+            line: &[],
+            block: &[("/*", "*/")],
+        },
+        "PHP" | "HCL" => CommentSyntax {
+            line: &["//", "#"],
+            block: &[("/*", "*/")],
+        },
+        "Python" | "Ruby" | "Shell" | "Perl" | "R" | "YAML" | "TOML" | "Makefile"
+        | "Dockerfile" | "Elixir" | "Nix" | "CMake" | "Julia" | "Nim" | "Crystal" => HASH,
+        "PowerShell" => CommentSyntax {
+            line: &["#"],
+            block: &[("<#", "#>")],
+        },
+        "HTML" | "XML" | "Markdown" | "SVG" | "Vue" | "Svelte" => MARKUP,
+        "SQL" | "PLSQL" | "PLpgSQL" | "TSQL" => CommentSyntax {
+            line: &["--"],
+            block: &[("/*", "*/")],
+        },
+        "Lua" => CommentSyntax {
+            line: &["--"],
+            block: &[("--[[", "]]")],
+        },
+        "Haskell" | "Elm" | "PureScript" => CommentSyntax {
+            line: &["--"],
+            block: &[("{-", "-}")],
+        },
+        "Ada" => CommentSyntax {
+            line: &["--"],
+            block: &[],
+        },
+        "OCaml" | "Standard ML" => CommentSyntax {
+            line: &[],
+            block: &[("(*", "*)")],
+        },
+        "F#" => CommentSyntax {
+            line: &["//"],
+            block: &[("(*", "*)")],
+        },
+        "Pascal" => CommentSyntax {
+            line: &["//"],
+            block: &[("{", "}"), ("(*", "*)")],
+        },
+        "Emacs Lisp" | "Common Lisp" | "Clojure" | "Scheme" | "Racket" | "Assembly" => SEMICOLON,
+        "INI" => CommentSyntax {
+            line: &[";", "#"],
+            block: &[],
+        },
+        "Erlang" | "TeX" | "MATLAB" => PERCENT,
+        "Vim Script" => CommentSyntax {
+            line: &["\""],
+            block: &[],
+        },
+        "Fortran" | "Fortran Free Form" => CommentSyntax {
+            line: &["!"],
+            block: &[],
+        },
+        _ => return None,
+    };
+    Some(syntax)
+}
+
+/// Choose the comment syntax for a source file from its extension.
+///
+/// Files in unrecognised languages accept any common comment style.
+pub(crate) fn for_path(path: &Path) -> CommentSyntax {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(languages::from_extension)
+        .and_then(|lang| for_language(lang.name))
+        .unwrap_or(FALLBACK)
+}
+
+/// Whether the comment syntax of the source file at `path` is known from its
+/// extension, rather than guessed from every common comment style.
+pub fn is_known_language(path: &Path) -> bool {
+    for_path(path) != FALLBACK
+}
+
+/// Choose the comment syntax for a language given by name or alias, such as
+/// a notebook's kernel language.
+pub(crate) fn for_name(name: &str) -> CommentSyntax {
+    languages::from_name(name)
+        .and_then(|lang| for_language(lang.name))
+        .unwrap_or(FALLBACK)
+}
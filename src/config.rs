@@ -0,0 +1,200 @@
+use crate::error::SnipsError;
+use crate::replace::Replacement;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the standalone configuration file.
+pub const CONFIG_FILE: &str = "snips.toml";
+
+/// Output format used by the command-line interface.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable, colored text.
+    #[default]
+    Text,
+    /// Machine-readable JSON.
+    Json,
+}
+
+/// How the language of a snippet's fence is named.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LanguageHint {
+    /// CodeMirror mode, e.g. `rust`, `clike` or `gfm`.
+    #[default]
+    Codemirror,
+    /// Linguist language name with spaces as hyphens, e.g. `Rust` or `C++`.
+    Linguist,
+    /// GitHub code fence alias: the first Linguist alias, e.g. `cpp`, or the
+    /// lowercased name for languages without one.
+    Github,
+    /// No language hint.
+    None,
+}
+
+/// Existing region conventions accepted alongside `snips-start`/`snips-end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RegionStyle {
+    /// `#region name` and `#endregion`, as directives or inside comments.
+    HashRegion,
+    /// IntelliJ-style `// region name` and `// endregion` comments.
+    Region,
+    /// AsciiDoc `tag::name[]` and `end::name[]` comments.
+    Asciidoc,
+    /// mdBook `ANCHOR: name` and `ANCHOR_END: name` comments.
+    Mdbook,
+}
+
+/// Region conventions enabled by default; `region` is opt-in because
+/// ordinary comments often begin with that word.
+pub const DEFAULT_REGION_STYLES: &[RegionStyle] = &[
+    RegionStyle::HashRegion,
+    RegionStyle::Asciidoc,
+    RegionStyle::Mdbook,
+];
+
+/// Project-level settings, read from `snips.toml` or `[package.metadata.snips]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Glob patterns selecting the markdown files to process.
+    pub docs: Vec<String>,
+    /// Glob patterns for files and directories to skip.
+    pub exclude: Vec<String>,
+    /// Language hints keyed by file extension, overriding detection.
+    pub languages: BTreeMap<String, String>,
+    /// Naming style for detected fence languages.
+    pub language_hint: LanguageHint,
+    /// Keyword used in markdown and source markers (`<!-- snips: -->`, `snips-start`).
+    pub marker: String,
+    /// Treat whitespace-only differences as changes when checking and diffing.
+    pub strict_whitespace: bool,
+    /// Keep `snips-start`/`snips-end` marker lines in rendered snippets.
+    pub keep_markers: bool,
+    /// Never replace a language already written on a snippet's fence.
+    pub pin_language: bool,
+    /// Line rendered in place of hidden source lines and between the parts of
+    /// a multi-part snippet, such as `// ...`. Nothing is added when unset.
+    pub elision: Option<String>,
+    /// Line placed between the parts of a marker that combines several sources.
+    pub part_separator: String,
+    /// Start each part of a combined marker with a comment naming its file.
+    pub part_headers: bool,
+    /// Command lines, or their leading arguments, that `<!-- snips: $ ... -->`
+    /// markers may run. Commands are refused when empty.
+    pub commands: Vec<String>,
+    /// Seconds a marker command may run before it is killed.
+    pub command_timeout: u64,
+    /// Substitutions applied to every rendered snippet.
+    pub replace: Vec<Replacement>,
+    /// Region conventions recognised as snippet markers.
+    pub regions: Vec<RegionStyle>,
+    /// Output format for the command-line interface.
+    pub format: OutputFormat,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            docs: Vec::new(),
+            exclude: Vec::new(),
+            languages: BTreeMap::new(),
+            language_hint: LanguageHint::default(),
+            marker: "snips".to_string(),
+            strict_whitespace: false,
+            keep_markers: false,
+            pin_language: false,
+            elision: None,
+            part_separator: String::new(),
+            part_headers: false,
+            commands: Vec::new(),
+            command_timeout: 30,
+            replace: Vec::new(),
+            regions: DEFAULT_REGION_STYLES.to_vec(),
+            format: OutputFormat::default(),
+        }
+    }
+}
+
+/// Subset of a `Cargo.toml` manifest that may carry snips settings.
+#[derive(Deserialize)]
+struct Manifest {
+    /// The `[package]` table.
+    package: Option<MetadataHolder>,
+    /// The `[workspace]` table.
+    workspace: Option<MetadataHolder>,
+}
+
+/// A manifest table with an optional `metadata` sub-table.
+#[derive(Deserialize)]
+struct MetadataHolder {
+    /// The `metadata` table.
+    metadata: Option<Metadata>,
+}
+
+/// The `metadata` table of a manifest.
+#[derive(Deserialize)]
+struct Metadata {
+    /// Settings under `metadata.snips`.
+    snips: Option<Config>,
+}
+
+impl Config {
+    /// Read settings from a `snips.toml` file.
+    pub fn from_file(path: &Path) -> Result<Self, SnipsError> {
+        let text = read_config(path)?;
+        toml::from_str(&text).map_err(|source| SnipsError::InvalidConfig {
+            file: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Read settings from the `snips` metadata of a `Cargo.toml`, if present.
+    fn from_manifest(path: &Path) -> Result<Option<Self>, SnipsError> {
+        let text = read_config(path)?;
+        let manifest: Manifest =
+            toml::from_str(&text).map_err(|source| SnipsError::InvalidConfig {
+                file: path.to_path_buf(),
+                source,
+            })?;
+        Ok([manifest.package, manifest.workspace]
+            .into_iter()
+            .flatten()
+            .find_map(|holder| holder.metadata.and_then(|m| m.snips)))
+    }
+
+    /// Search `start` and its ancestors for project settings.
+    ///
+    /// At each level a `snips.toml` takes precedence over `snips` metadata in
+    /// `Cargo.toml`. Returns the file the settings came from together with the
+    /// parsed settings, or `None` when no configuration exists.
+    pub fn discover(start: &Path) -> Result<Option<(PathBuf, Self)>, SnipsError> {
+        for dir in start.ancestors() {
+            let standalone = dir.join(CONFIG_FILE);
+            if standalone.is_file() {
+                let config = Self::from_file(&standalone)?;
+                return Ok(Some((standalone, config)));
+            }
+            let manifest = dir.join("Cargo.toml");
+            if manifest.is_file()
+                && let Some(config) = Self::from_manifest(&manifest)?
+            {
+                return Ok(Some((manifest, config)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Read a configuration file to a string.
+fn read_config(path: &Path) -> Result<String, SnipsError> {
+    fs::read_to_string(path).map_err(|source| SnipsError::FileReadFailed {
+        file: path.to_path_buf(),
+        source,
+    })
+}
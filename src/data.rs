@@ -0,0 +1,353 @@
+use crate::error::SnipsError;
+use std::iter;
+use std::path::Path;
+use tree_sitter::{Language, Node, Parser};
+
+/// A structured data format with subtree selectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DataFormat {
+    /// JSON, addressed by JSON pointer (`/properties/server`).
+    Json,
+    /// TOML, addressed by dotted key (`dependencies.serde`).
+    Toml,
+    /// YAML, addressed by dotted key (`services.web`).
+    Yaml,
+}
+
+impl DataFormat {
+    /// The tree-sitter grammar for the format.
+    fn language(self) -> Language {
+        match self {
+            Self::Json => tree_sitter_json::LANGUAGE.into(),
+            Self::Toml => tree_sitter_toml_ng::LANGUAGE.into(),
+            Self::Yaml => tree_sitter_yaml::LANGUAGE.into(),
+        }
+    }
+}
+
+/// Outcome of looking up a path: the selected source ranges in document
+/// order, or the paths that were available where the lookup failed.
+type Lookup = Result<Vec<(usize, usize)>, Vec<String>>;
+
+/// Extract the subtree at `path` from `content` in the given `format`.
+///
+/// The subtree is cut from the original text, so formatting and comments
+/// inside it are kept. TOML and YAML selections include their key and any
+/// comment lines directly above it; JSON selections are the bare value.
+/// Matching TOML tables separated by other tables are joined by a blank line.
+pub(crate) fn extract_data(
+    content: &str,
+    format: DataFormat,
+    path: &str,
+    file: &Path,
+) -> Result<String, SnipsError> {
+    let parse_failed = |message: String| SnipsError::SourceParseFailed {
+        file: file.to_path_buf(),
+        message,
+    };
+    let mut parser = Parser::new();
+    parser
+        .set_language(&format.language())
+        .map_err(|e| parse_failed(e.to_string()))?;
+    let tree = parser
+        .parse(content, None)
+        .ok_or_else(|| parse_failed("parser produced no syntax tree".to_string()))?;
+    let root = tree.root_node();
+    let source = content.as_bytes();
+
+    let found = match format {
+        DataFormat::Json => {
+            if !path.is_empty() && !path.starts_with('/') {
+                return Err(SnipsError::InvalidJsonPointer {
+                    file: file.to_path_buf(),
+                    pointer: path.to_string(),
+                });
+            }
+            json_lookup(root, path, source)
+        }
+        DataFormat::Toml => toml_lookup(root, &split_dotted(path), source),
+        DataFormat::Yaml => yaml_lookup(root, &split_dotted(path), source),
+    };
+    let ranges = found.map_err(|available| SnipsError::ItemNotFound {
+        file: file.to_path_buf(),
+        item: path.to_string(),
+        available_items: if available.is_empty() {
+            "none".to_string()
+        } else {
+            available.join(", ")
+        },
+    })?;
+    let parts: Vec<String> = ranges
+        .into_iter()
+        .map(|(start, end)| match format {
+            DataFormat::Json => reindent(content, start, end),
+            DataFormat::Toml | DataFormat::Yaml => {
+                let start = leading_comments_start(content, line_start(content, start));
+                let end = trailing_comment_end(content, end);
+                trim_trailing_comments(&content[start..end]).to_string()
+            }
+        })
+        .collect();
+    Ok(parts.join("\n\n"))
+}
+
+/// Split a dotted key path, allowing quoted segments such as `"a.b".c`.
+fn split_dotted(path: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    for c in path.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '.') => segments.push(current.split_off(0)),
+            (None, c) => current.push(c),
+        }
+    }
+    segments.push(current);
+    segments
+        .into_iter()
+        .map(|segment| segment.trim().to_string())
+        .collect()
+}
+
+/// The text of `node`.
+fn text<'a>(node: Node<'_>, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or_default()
+}
+
+/// Remove surrounding quotes from a key.
+fn unquote(key: &str) -> &str {
+    let key = key.trim();
+    ['"', '\'']
+        .iter()
+        .find_map(|&q| key.strip_prefix(q).and_then(|k| k.strip_suffix(q)))
+        .unwrap_or(key)
+}
+
+/// Named children of `node`, skipping comments.
+fn children(node: Node<'_>) -> Vec<Node<'_>> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|child| child.kind() != "comment")
+        .collect()
+}
+
+/// Follow a JSON pointer from the document `root` to a value.
+fn json_lookup(root: Node<'_>, pointer: &str, source: &[u8]) -> Lookup {
+    let mut node = children(root).into_iter().next().ok_or_else(Vec::new)?;
+    let tokens = pointer
+        .strip_prefix('/')
+        .map(|rest| rest.split('/').collect::<Vec<_>>())
+        .unwrap_or_default();
+    let mut prefix = String::new();
+    for token in tokens {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        let items = children(node);
+        let next = match node.kind() {
+            "object" => items.iter().find_map(|pair| {
+                let key = pair.child_by_field_name("key")?;
+                (unquote(text(key, source)) == token)
+                    .then(|| pair.child_by_field_name("value"))
+                    .flatten()
+            }),
+            "array" => token
+                .parse()
+                .ok()
+                .and_then(|idx: usize| items.get(idx).copied()),
+            _ => None,
+        };
+        let Some(next) = next else {
+            return Err(match node.kind() {
+                "object" => items
+                    .iter()
+                    .filter_map(|pair| pair.child_by_field_name("key"))
+                    .map(|key| format!("{prefix}/{}", unquote(text(key, source))))
+                    .collect(),
+                "array" => (0..items.len())
+                    .map(|idx| format!("{prefix}/{idx}"))
+                    .collect(),
+                _ => Vec::new(),
+            });
+        };
+        prefix = format!("{prefix}/{token}");
+        node = next;
+    }
+    Ok(vec![(node.start_byte(), node.end_byte())])
+}
+
+/// The dotted key segments of a TOML key node.
+fn toml_key(node: Node<'_>, source: &[u8]) -> Vec<String> {
+    match node.kind() {
+        "dotted_key" => children(node)
+            .into_iter()
+            .flat_map(|child| toml_key(child, source))
+            .collect(),
+        _ => vec![unquote(text(node, source)).to_string()],
+    }
+}
+
+/// The key of a TOML pair, table or array table, if it has one.
+fn toml_key_of(node: Node<'_>, source: &[u8]) -> Option<Vec<String>> {
+    children(node)
+        .into_iter()
+        .next()
+        .filter(|key| matches!(key.kind(), "bare_key" | "dotted_key" | "quoted_key"))
+        .map(|key| toml_key(key, source))
+}
+
+/// Find the TOML tables under `path`, or the key/value pair at `path`.
+///
+/// A table path selects the table and its subtables; array tables select
+/// every element. Each run of adjacent matching tables is one range.
+fn toml_lookup(root: Node<'_>, path: &[String], source: &[u8]) -> Lookup {
+    let mut tables: Vec<(usize, usize)> = Vec::new();
+    // Whether the previous top-level node was a matching table.
+    let mut in_run = false;
+    let mut pair = None;
+    let mut available = Vec::new();
+    for node in children(root) {
+        let key = toml_key_of(node, source).unwrap_or_default();
+        if node.kind() == "pair" {
+            if key == path {
+                pair = Some((node.start_byte(), node.end_byte()));
+            }
+            available.push(key.join("."));
+            in_run = false;
+            continue;
+        }
+        let matches = key.starts_with(path);
+        match tables.last_mut() {
+            Some((_, end)) if matches && in_run => *end = node.end_byte(),
+            _ if matches => tables.push((node.start_byte(), node.end_byte())),
+            _ => {}
+        }
+        in_run = matches;
+        available.push(key.join("."));
+        for child in children(node).into_iter().filter(|c| c.kind() == "pair") {
+            let full: Vec<String> = key
+                .iter()
+                .cloned()
+                .chain(toml_key_of(child, source).unwrap_or_default())
+                .collect();
+            if full == path {
+                pair = Some((child.start_byte(), child.end_byte()));
+            }
+            available.push(full.join("."));
+        }
+    }
+    available.dedup();
+    if tables.is_empty() {
+        pair.map(|range| vec![range]).ok_or(available)
+    } else {
+        Ok(tables)
+    }
+}
+
+/// Unwrap YAML node wrappers down to the mapping, sequence or scalar inside.
+fn yaml_content(mut node: Node<'_>) -> Node<'_> {
+    while matches!(
+        node.kind(),
+        "stream" | "document" | "block_node" | "flow_node"
+    ) {
+        let Some(inner) = children(node)
+            .into_iter()
+            .find(|child| !matches!(child.kind(), "anchor" | "tag"))
+        else {
+            break;
+        };
+        node = inner;
+    }
+    node
+}
+
+/// Follow a dotted key path from the YAML `root` to a key/value pair or
+/// sequence item.
+fn yaml_lookup(root: Node<'_>, path: &[String], source: &[u8]) -> Lookup {
+    let mut node = root;
+    let mut selected = None;
+    let mut prefix = String::new();
+    for token in path {
+        let container = yaml_content(node);
+        let items = children(container);
+        let next = match container.kind() {
+            "block_mapping" | "flow_mapping" => items.iter().copied().find(|pair| {
+                pair.child_by_field_name("key")
+                    .is_some_and(|key| unquote(text(key, source)) == token)
+            }),
+            "block_sequence" | "flow_sequence" => token
+                .parse()
+                .ok()
+                .and_then(|idx: usize| items.get(idx).copied()),
+            _ => None,
+        };
+        let Some(next) = next else {
+            return Err(items
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| match item.child_by_field_name("key") {
+                    Some(key) => format!("{prefix}{}", unquote(text(key, source))),
+                    None => format!("{prefix}{idx}"),
+                })
+                .collect());
+        };
+        prefix = format!("{prefix}{token}.");
+        selected = Some(next);
+        node = next.child_by_field_name("value").unwrap_or(next);
+        if next.kind() == "block_sequence_item" {
+            node = children(next).into_iter().next().unwrap_or(next);
+        }
+    }
+    let selected = selected.unwrap_or(root);
+    Ok(vec![(selected.start_byte(), selected.end_byte())])
+}
+
+/// Byte offset of the start of the line containing `offset`.
+fn line_start(content: &str, offset: usize) -> usize {
+    content[..offset].rfind('\n').map_or(0, |idx| idx + 1)
+}
+
+/// Move the line-aligned `start` back over `#` comment lines directly above it.
+fn leading_comments_start(content: &str, mut start: usize) -> usize {
+    while start > 0 {
+        let previous = line_start(content, start - 1);
+        if !content[previous..start].trim_start().starts_with('#') {
+            break;
+        }
+        start = previous;
+    }
+    start
+}
+
+/// Move `end` past a `#` comment that ends its line.
+fn trailing_comment_end(content: &str, end: usize) -> usize {
+    let line_end = content[end..]
+        .find('\n')
+        .map_or(content.len(), |idx| end + idx);
+    let rest = content[end..line_end].trim_start();
+    if rest.starts_with('#') { line_end } else { end }
+}
+
+/// Drop trailing blank and `#` comment lines, which introduce what follows.
+fn trim_trailing_comments(text: &str) -> &str {
+    let mut text = text.trim_end();
+    while let Some(idx) = text.rfind('\n') {
+        if !text[idx + 1..].trim_start().starts_with('#') {
+            break;
+        }
+        text = text[..idx].trim_end();
+    }
+    text
+}
+
+/// Cut `start..end` from `content`, removing the indentation of the line it
+/// starts on from the following lines.
+fn reindent(content: &str, start: usize, end: usize) -> String {
+    let line = &content[line_start(content, start)..];
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let mut lines = content[start..end].lines();
+    let first = lines.next().unwrap_or_default();
+    let rest = lines.map(|l| l.strip_prefix(indent).unwrap_or(l));
+    iter::once(first).chain(rest).collect::<Vec<_>>().join("\n")
+}
@@ -0,0 +1,122 @@
+use crate::error::SnipsError;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use std::path::{Path, PathBuf};
+
+/// Directory names that are never descended into during discovery.
+const SKIPPED_DIRS: &[&str] = &["target"];
+
+/// Filters applied while discovering markdown files under a directory.
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveryOptions {
+    /// Glob patterns a file must match to be processed. When empty, all
+    /// `.md` and `.markdown` files are included.
+    pub include: Vec<String>,
+    /// Glob patterns for files and directories to skip.
+    pub exclude: Vec<String>,
+}
+
+/// Determine whether `path` points to a markdown file.
+pub fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext.to_ascii_lowercase().as_str(), "md" | "markdown"))
+}
+
+/// Compile a list of glob patterns into a matcher.
+fn build_globset(patterns: &[String]) -> Result<GlobSet, SnipsError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|source| SnipsError::InvalidGlob {
+            pattern: pattern.clone(),
+            source,
+        })?;
+        builder.add(glob);
+    }
+    builder.build().map_err(|source| SnipsError::InvalidGlob {
+        pattern: patterns.join(", "),
+        source,
+    })
+}
+
+/// Find markdown files beneath `roots`.
+///
+/// Files named explicitly are returned as-is. Directories are walked
+/// recursively, honouring `.gitignore` and `.ignore` files, skipping hidden
+/// entries and `target/` directories, and applying the include and exclude
+/// globs from `options` to paths relative to the directory being walked.
+pub fn discover_markdown_files(
+    roots: &[PathBuf],
+    options: &DiscoveryOptions,
+) -> Result<Vec<PathBuf>, SnipsError> {
+    discover(roots, options, is_markdown)
+}
+
+/// Find files of any type beneath `roots`, walking directories the same way
+/// as [`discover_markdown_files`]. Without include globs every file matches.
+pub fn discover_files(
+    roots: &[PathBuf],
+    options: &DiscoveryOptions,
+) -> Result<Vec<PathBuf>, SnipsError> {
+    discover(roots, options, |_| true)
+}
+
+/// Walk `roots`, keeping files accepted by `default_filter` when no include
+/// globs are given.
+fn discover(
+    roots: &[PathBuf],
+    options: &DiscoveryOptions,
+    default_filter: fn(&Path) -> bool,
+) -> Result<Vec<PathBuf>, SnipsError> {
+    let include = build_globset(&options.include)?;
+    let exclude = build_globset(&options.exclude)?;
+    let mut discovered = Vec::new();
+
+    for root in roots {
+        if !root.is_dir() {
+            discovered.push(root.clone());
+            continue;
+        }
+
+        let prune_root = root.clone();
+        let prune = exclude.clone();
+        let walker = WalkBuilder::new(root)
+            .require_git(false)
+            .filter_entry(move |entry| {
+                let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+                if is_dir
+                    && entry.depth() > 0
+                    && SKIPPED_DIRS.iter().any(|d| entry.file_name() == *d)
+                {
+                    return false;
+                }
+                let rel = entry
+                    .path()
+                    .strip_prefix(&prune_root)
+                    .unwrap_or(entry.path());
+                entry.depth() == 0 || !prune.is_match(rel)
+            })
+            .build();
+
+        for entry in walker {
+            let entry = entry?;
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            let path = entry.path();
+            let rel = path.strip_prefix(root).unwrap_or(path);
+            let included = if options.include.is_empty() {
+                default_filter(path)
+            } else {
+                include.is_match(rel)
+            };
+            if included {
+                discovered.push(path.to_path_buf());
+            }
+        }
+    }
+
+    discovered.sort();
+    discovered.dedup();
+    Ok(discovered)
+}
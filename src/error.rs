@@ -1,6 +1,7 @@
 use std::io;
 use std::path::PathBuf;
 use thiserror::Error;
+use toml::de::Error as TomlError;
 
 #[derive(Error, Debug)]
 /// Errors produced while processing snippets.
@@ -36,7 +37,7 @@ pub enum SnipsError {
     },
     /// A marker does not match the expected syntax.
     #[error(
-        "invalid marker format in {file}:{line}\n  {content}\n  Expected format: <!-- snips: path/to/file.ext --> or <!-- snips: path/to/file.ext#snippet_name -->"
+        "invalid marker format in {file}:{line}\n  {content}\n  Expected format: <!-- snips: path[@rev][#selector][, path...] [options] --> or <!-- snips: $ command -->\n  Selectors: name, L10-L20, /start/../end/ or kind=value (fn=, item=, sig=, symbol=, toml=, yaml=, json=, cell=, tag=, heading=); see the Features section of the README"
     )]
     InvalidMarker {
         /// Markdown file containing the invalid marker.
@@ -46,6 +47,22 @@ pub enum SnipsError {
         /// Full text of the offending line.
         content: String,
     },
+    /// An option on a marker is unknown or has an invalid value.
+    #[error(
+        "invalid marker option `{option}` in {file}:{line}: {reason}\nSupported options: {supported}"
+    )]
+    InvalidMarkerOption {
+        /// Markdown file containing the marker.
+        file: PathBuf,
+        /// One-based line number of the marker.
+        line: usize,
+        /// The option as written on the marker.
+        option: String,
+        /// Why the option was rejected.
+        reason: String,
+        /// Comma-separated list of supported options.
+        supported: String,
+    },
     /// A requested snippet name is missing from the source file.
     #[error(
         "snippet `{snippet_name}` not found in {file}\nAvailable snippets: {available_snippets}"
@@ -58,12 +75,296 @@ pub enum SnipsError {
         /// Comma-separated list of snippets that were found.
         available_snippets: String,
     },
+    /// A `heading=` selector names a heading missing from the markdown file.
+    #[error("heading `{heading}` not found in {file}\nAvailable headings: {available}")]
+    HeadingNotFound {
+        /// Markdown file that was searched.
+        file: PathBuf,
+        /// The heading as written in the marker.
+        heading: String,
+        /// Comma-separated anchors of the headings that were found.
+        available: String,
+    },
+    /// A raw inclusion was opened but never closed.
+    #[error(
+        "raw inclusion starting at line {start_line} in {file} is missing its closing `{close}`"
+    )]
+    UnterminatedRawBlock {
+        /// Markdown file containing the inclusion.
+        file: PathBuf,
+        /// One-based line number of the opening marker.
+        start_line: usize,
+        /// The expected closing marker.
+        close: String,
+    },
+    /// A line range selector does not fit within the source file.
+    #[error("invalid line range `{range}` for {file} ({line_count} lines)")]
+    InvalidLineRange {
+        /// Source file the range was applied to.
+        file: PathBuf,
+        /// The range as written in the marker.
+        range: String,
+        /// Number of lines in the source file.
+        line_count: usize,
+    },
+    /// A `snips-replace` annotation is not a `find => with` rule.
+    #[error("invalid replacement rule `{rule}` at {file}:{line}: expected `find => with`")]
+    InvalidReplaceRule {
+        /// Source file containing the annotation.
+        file: PathBuf,
+        /// One-based line number of the annotation.
+        line: usize,
+        /// The rule as written.
+        rule: String,
+    },
+    /// A command marker names a command missing from the `commands` allowlist.
+    #[error(
+        "command `{0}` is not allowed; add it, or a prefix of it, to `commands` in the configuration"
+    )]
+    CommandNotAllowed(String),
+    /// A command marker could not be split into arguments.
+    #[error("invalid command `{command}`: {reason}")]
+    InvalidCommand {
+        /// The command line as written in the marker.
+        command: String,
+        /// Why the command line could not be split.
+        reason: String,
+    },
+    /// A command could not be started or its output could not be read.
+    #[error("failed to run command `{command}`: {source}")]
+    CommandSpawnFailed {
+        /// The command line as written in the marker.
+        command: String,
+        /// Underlying OS error.
+        #[source]
+        source: io::Error,
+    },
+    /// A command exited with a non-zero status.
+    #[error("command `{command}` failed ({status})\n{stderr}")]
+    CommandFailed {
+        /// The command line as written in the marker.
+        command: String,
+        /// Description of the exit status.
+        status: String,
+        /// Captured standard error.
+        stderr: String,
+    },
+    /// A command ran longer than the configured timeout.
+    #[error("command `{command}` timed out after {seconds}s")]
+    CommandTimedOut {
+        /// The command line as written in the marker.
+        command: String,
+        /// Timeout that was exceeded, in seconds.
+        seconds: u64,
+    },
+    /// A `path@rev` reference names a revision that does not exist.
+    #[error("git revision `{rev}` not found for {file}")]
+    GitRevisionNotFound {
+        /// Source file that was referenced.
+        file: PathBuf,
+        /// The revision as written in the marker.
+        rev: String,
+    },
+    /// A `path@rev` reference names a file that does not exist at the revision.
+    #[error("{file} does not exist at git revision `{rev}`")]
+    GitPathNotFound {
+        /// Source file that was referenced.
+        file: PathBuf,
+        /// The revision as written in the marker.
+        rev: String,
+    },
+    /// Git could not be run or could not read the repository.
+    #[error("failed to read {file} from git: {message}")]
+    GitFailed {
+        /// Source file that was referenced.
+        file: PathBuf,
+        /// What went wrong, usually git's own error output.
+        message: String,
+    },
+    /// A `cell=` selector names cells outside the notebook.
+    #[error("cell `{cell}` not found in {file} ({cell_count} code cells, numbered from 1)")]
+    NotebookCellNotFound {
+        /// Notebook that was searched.
+        file: PathBuf,
+        /// The cell number or range as written in the marker.
+        cell: String,
+        /// Number of code cells in the notebook.
+        cell_count: usize,
+    },
+    /// A `tag=` selector names a tag no notebook cell carries.
+    #[error("no cell tagged `{tag}` in {file}\nAvailable tags: {available}")]
+    NotebookTagNotFound {
+        /// Notebook that was searched.
+        file: PathBuf,
+        /// The tag as written in the marker.
+        tag: String,
+        /// Comma-separated tags found in the notebook.
+        available: String,
+    },
+    /// A cell or tag selector was used on a file that is not a notebook.
+    #[error("selector `{selector}` only applies to notebooks, but {file} is not a notebook")]
+    NotANotebook {
+        /// Source file that was referenced.
+        file: PathBuf,
+        /// The selector as written in the marker.
+        selector: String,
+    },
+    /// The `outputs` option was used where there are no notebook cells to
+    /// take outputs from.
+    #[error(
+        "outputs are only available for whole notebooks and for notebook cells selected by number, tag or name ({file})"
+    )]
+    OutputsUnsupported {
+        /// Source file that was referenced.
+        file: PathBuf,
+    },
+    /// A selector pattern is not a valid regular expression.
+    #[error("invalid pattern `{pattern}`: {source}")]
+    InvalidPattern {
+        /// The pattern as written in the marker.
+        pattern: String,
+        /// Underlying regex error.
+        #[source]
+        source: regex::Error,
+    },
+    /// A selector pattern matched no line in the source file.
+    #[error("pattern `{pattern}` did not match any line in {file} at or after line {start_line}")]
+    PatternNotMatched {
+        /// Source file that was searched.
+        file: PathBuf,
+        /// The pattern that failed to match.
+        pattern: String,
+        /// One-based line number where the search started.
+        start_line: usize,
+    },
+    /// A `kind=value` selector uses an unknown kind.
+    #[error("unknown selector `{selector}`\nSupported selector kinds: {supported}")]
+    UnknownSelector {
+        /// The selector as written in the marker.
+        selector: String,
+        /// Comma-separated list of supported selector kinds.
+        supported: String,
+    },
+    /// A `json=` selector is not a JSON pointer (RFC 6901).
+    #[error("invalid JSON pointer `{pointer}` for {file}: it must be empty or start with `/`")]
+    InvalidJsonPointer {
+        /// Data file the pointer was applied to.
+        file: PathBuf,
+        /// The pointer as written in the marker.
+        pointer: String,
+    },
+    /// A source file could not be parsed to locate an item.
+    #[error("failed to parse {file}: {message}")]
+    SourceParseFailed {
+        /// Source file that failed to parse.
+        file: PathBuf,
+        /// Parser error message with location.
+        message: String,
+    },
+    /// Symbol extraction was requested for a language without a parser.
+    #[error(
+        "symbol selectors are not supported for {language} ({file})\nSupported languages: {supported}"
+    )]
+    UnsupportedLanguage {
+        /// Source file the selector was applied to.
+        file: PathBuf,
+        /// Language detected from the file extension.
+        language: String,
+        /// Comma-separated list of supported languages.
+        supported: String,
+    },
+    /// A requested item is missing from the source file.
+    #[error("item `{item}` not found in {file}\nAvailable items: {available_items}")]
+    ItemNotFound {
+        /// Source file that was searched.
+        file: PathBuf,
+        /// The requested item.
+        item: String,
+        /// Comma-separated list of items that were found.
+        available_items: String,
+    },
     /// A snippet start marker was found without a matching end marker.
-    #[error("unterminated snippet `{1}` in {0}")]
-    UnterminatedSnippet(PathBuf, String),
+    #[error("unterminated snippet `{name}` started at {file}:{line}")]
+    UnterminatedSnippet {
+        /// Source file containing the start marker.
+        file: PathBuf,
+        /// Name of the snippet that is never closed.
+        name: String,
+        /// One-based line number of the start marker.
+        line: usize,
+    },
+    /// A snippet is started again while it is still open.
+    #[error(
+        "snippet `{name}` started again at {file}:{line} while still open (started on line {first_line})"
+    )]
+    DuplicateSnippet {
+        /// Source file containing the repeated start marker.
+        file: PathBuf,
+        /// The repeated snippet name.
+        name: String,
+        /// One-based line number of the repeated start marker.
+        line: usize,
+        /// One-based line number of the start marker that is still open.
+        first_line: usize,
+    },
+    /// An end marker appears while no snippet is open.
+    #[error("end marker without a matching start at {file}:{line}")]
+    UnmatchedEndMarker {
+        /// Source file containing the marker.
+        file: PathBuf,
+        /// One-based line number of the end marker.
+        line: usize,
+    },
+    /// A named end marker does not name any open snippet.
+    #[error("end marker for `{found}` at {file}:{line} does not match open snippet `{expected}`")]
+    MismatchedEndMarker {
+        /// Source file containing the marker.
+        file: PathBuf,
+        /// Name of the innermost open snippet.
+        expected: String,
+        /// Name given by the end marker.
+        found: String,
+        /// One-based line number of the end marker.
+        line: usize,
+    },
+    /// A snippet ends while a snippet opened inside it is still open.
+    #[error(
+        "snippet `{name}` ends at {file}:{line} while `{inner}` is still open; regions must nest"
+    )]
+    OverlappingSnippets {
+        /// Source file containing the marker.
+        file: PathBuf,
+        /// Name of the snippet being closed.
+        name: String,
+        /// Name of the snippet opened inside it that is still open.
+        inner: String,
+        /// One-based line number of the end marker.
+        line: usize,
+    },
     /// No markdown files were found in the working directory.
     #[error("no markdown files found in {0}")]
     NoMarkdownFiles(PathBuf),
+    /// A configuration file could not be parsed.
+    #[error("invalid configuration in {file}: {source}")]
+    InvalidConfig {
+        /// Configuration file that failed to parse.
+        file: PathBuf,
+        /// Underlying TOML error.
+        #[source]
+        source: TomlError,
+    },
+    /// A glob pattern used for file discovery could not be compiled.
+    #[error("invalid glob pattern `{pattern}`: {source}")]
+    InvalidGlob {
+        /// The offending pattern.
+        pattern: String,
+        /// Underlying glob parse error.
+        #[source]
+        source: globset::Error,
+    },
+    /// Walking a directory tree failed.
+    #[error("failed to walk directory: {0}")]
+    Walk(#[from] ignore::Error),
     /// Any other I/O error propagated from the filesystem.
     #[error("IO error: {0}")]
     Io(#[from] io::Error),
@@ -0,0 +1,62 @@
+use crate::error::SnipsError;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Run `git` with `args` in `dir`, describing launch failures as a message.
+fn git(dir: &Path, args: &[&str]) -> Result<Output, String> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|err| format!("could not run git: {err}"))
+}
+
+/// Read the content of `path` as of the git revision `rev`.
+///
+/// The file is looked up in the local repository containing it, so it need
+/// not exist in the working tree, and no remote is contacted.
+pub(crate) fn read_at_revision(path: &Path, rev: &str) -> Result<String, SnipsError> {
+    let failed = |message: String| SnipsError::GitFailed {
+        file: path.to_path_buf(),
+        message,
+    };
+    // Git would read a leading dash as an option rather than a revision.
+    if rev.starts_with('-') {
+        return Err(failed(format!("revision `{rev}` must not start with `-`")));
+    }
+    // The file's directory may be gone from the working tree, so run git from
+    // the nearest directory that still exists.
+    let dir = path
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.is_dir())
+        .unwrap_or(Path::new("."));
+    let relative = path
+        .strip_prefix(dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    let commit = format!("{rev}^{{commit}}");
+    let verified = git(dir, &["rev-parse", "--verify", "--quiet", &commit]).map_err(failed)?;
+    if !verified.status.success() {
+        let message = String::from_utf8_lossy(&verified.stderr).trim().to_string();
+        if !message.is_empty() {
+            return Err(failed(message));
+        }
+        return Err(SnipsError::GitRevisionNotFound {
+            file: path.to_path_buf(),
+            rev: rev.to_string(),
+        });
+    }
+    let object = format!("{rev}:./{relative}");
+    let blob = git(dir, &["cat-file", "blob", &object]).map_err(failed)?;
+    if !blob.status.success() {
+        return Err(SnipsError::GitPathNotFound {
+            file: path.to_path_buf(),
+            rev: rev.to_string(),
+        });
+    }
+    String::from_utf8(blob.stdout).map_err(|_| failed("file is not valid UTF-8".to_string()))
+}
@@ -2,15 +2,45 @@
 
 //! Snips keeps markdown snippets synchronized with their source files.
 
+/// Running allowlisted commands whose output is embedded as snippets.
+pub mod command;
+/// Per-language comment syntax used to recognise source markers.
+pub mod comment;
+/// Project configuration loaded from `snips.toml` or `Cargo.toml` metadata.
+pub mod config;
+/// Subtree extraction for JSON, YAML and TOML sources.
+pub mod data;
+/// Recursive discovery of markdown files.
+pub mod discovery;
 /// Error definitions used across the crate.
 pub mod error;
+/// Reading source files as of a git revision.
+pub mod git;
+/// Heading-based section extraction for markdown sources.
+pub mod markdown;
+/// Jupyter notebooks as snippet sources.
+pub mod notebook;
+/// Key/value options written on markdown markers.
+pub mod options;
 /// Core processing logic for scanning and updating markdown files.
 pub mod processor;
+/// Text substitutions applied to rendered snippets.
+pub mod replace;
+/// Rust item extraction backed by a full parser.
+pub mod rust_item;
 /// Helpers for locating and extracting snippets from source files.
 pub mod snippet;
+/// Language-aware symbol extraction using tree-sitter grammars.
+pub mod symbol;
 
+pub use config::{Config, LanguageHint, OutputFormat, RegionStyle};
+pub use discovery::{DiscoveryOptions, discover_files, discover_markdown_files};
 pub use error::SnipsError;
+pub use options::MarkerOptions;
 pub use processor::{
-    RenderSummary, SnippetDiff, SnippetLocator, SnippetReport, diff_file, sync_snippets_in_file,
-    sync_snippets_in_file_with_summary,
+    RenderSummary, SnippetDiff, SnippetLocator, SnippetPart, SnippetReport, diff_file,
+    diff_file_with_config, sync_snippets_in_file, sync_snippets_in_file_with_summary,
+    sync_snippets_with_config,
 };
+pub use replace::Replacement;
+pub use snippet::lint_source;
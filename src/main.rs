@@ -2,13 +2,17 @@
 
 //! Command-line interface for synchronizing snippets.
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use owo_colors::OwoColorize;
+use serde_json::{Value, json};
+use snips::comment::is_known_language;
 use snips::{
-    RenderSummary, SnippetReport, SnipsError, diff_file, sync_snippets_in_file_with_summary,
+    Config, DiscoveryOptions, LanguageHint, OutputFormat, RenderSummary, SnippetReport, SnipsError,
+    diff_file_with_config, discover_files, discover_markdown_files, lint_source,
+    sync_snippets_with_config,
 };
 use std::path::{Path, PathBuf};
-use std::{env, error::Error, fs, process};
+use std::{env, error::Error, process};
 
 /// Available operating modes for the CLI.
 enum Mode {
@@ -19,12 +23,31 @@ enum Mode {
     },
     /// Display diffs between embedded snippets and sources.
     Diff,
+    /// Validate snippet markers in source files.
+    LintSources {
+        /// Source files or directories to check.
+        paths: Vec<PathBuf>,
+    },
+}
+
+/// Subcommands that replace the default render behaviour.
+#[derive(Subcommand)]
+enum Command {
+    /// Check source files for reopened, unmatched, mismatched and overlapping snippet markers
+    LintSources {
+        /// Source files or directories to check; defaults to the current directory when omitted.
+        #[arg(num_args = 0..)]
+        paths: Vec<PathBuf>,
+    },
 }
 
 #[derive(Parser)]
 #[command(version, about)]
 /// Parsed command-line arguments.
 struct Cli {
+    /// Subcommand to run instead of rendering
+    #[command(subcommand)]
+    command: Option<Command>,
     /// Quiet mode
     #[arg(long, action = clap::ArgAction::SetTrue)]
     quiet: bool,
@@ -34,7 +57,37 @@ struct Cli {
     /// Show diff of changes
     #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "check")]
     diff: bool,
-    /// Files to process; defaults to all markdown files in the current directory when omitted.
+    /// Only process files matching this glob (repeatable); overrides `docs` in the config
+    #[arg(long, value_name = "GLOB")]
+    include: Vec<String>,
+    /// Skip files and directories matching this glob (repeatable); overrides `exclude` in the config
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+    /// Configuration file to use instead of searching for snips.toml or Cargo.toml
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+    /// Marker keyword used in markdown and source files
+    #[arg(long, value_name = "KEYWORD")]
+    marker: Option<String>,
+    /// Treat whitespace-only differences as changes
+    #[arg(long, action = clap::ArgAction::SetTrue, overrides_with = "no_strict_whitespace")]
+    strict_whitespace: bool,
+    /// Ignore whitespace-only differences, even when the config enables strict whitespace
+    #[arg(long, action = clap::ArgAction::SetTrue, overrides_with = "strict_whitespace")]
+    no_strict_whitespace: bool,
+    /// Keep nested snippet marker lines in rendered snippets
+    #[arg(long, action = clap::ArgAction::SetTrue, overrides_with = "no_keep_markers")]
+    keep_markers: bool,
+    /// Strip nested snippet marker lines, even when the config keeps them
+    #[arg(long, action = clap::ArgAction::SetTrue, overrides_with = "keep_markers")]
+    no_keep_markers: bool,
+    /// Naming style for fence languages
+    #[arg(long, value_name = "STYLE", value_enum)]
+    language_hint: Option<LanguageHint>,
+    /// Output format
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
+    /// Files or directories to process; defaults to the current directory when omitted.
     #[arg(num_args = 0..)]
     files: Vec<PathBuf>,
 }
@@ -52,37 +105,75 @@ fn print_diff(old: &str, new: &str) {
     }
 }
 
-/// Determine whether `path` points to a markdown file.
-fn is_markdown(path: &Path) -> bool {
-    path.extension()
-        .and_then(|ext| ext.to_str())
-        .is_some_and(|ext| matches!(ext.to_ascii_lowercase().as_str(), "md" | "markdown"))
-}
-
 /// Convert `path` to a string relative to `cwd` when possible.
 fn relative_display(path: &Path, cwd: &Path) -> String {
     path.strip_prefix(cwd).unwrap_or(path).display().to_string()
 }
 
-/// Determine which files to operate on, defaulting to all markdown files in the CWD.
-fn resolve_files(cli_files: &[PathBuf]) -> Result<Vec<PathBuf>, Box<dyn Error>> {
-    if !cli_files.is_empty() {
-        return Ok(cli_files.to_vec());
-    }
-
-    let cwd = env::current_dir()?;
-    let mut discovered = Vec::new();
-    for entry in fs::read_dir(&cwd)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_file() && is_markdown(&path) {
-            discovered.push(path);
+/// Load project settings and apply command-line overrides.
+///
+/// Returns the settings along with the directory that default discovery
+/// starts from: the directory holding the configuration file, or `cwd`.
+fn load_config(cli: &Cli, cwd: &Path) -> Result<(Config, PathBuf), SnipsError> {
+    let found = match &cli.config {
+        Some(path) => Some((path.clone(), Config::from_file(path)?)),
+        None => Config::discover(cwd)?,
+    };
+    let (mut config, root) = match found {
+        Some((file, config)) => {
+            let root = file
+                .parent()
+                .filter(|p| !p.as_os_str().is_empty())
+                .map_or_else(|| cwd.to_path_buf(), Path::to_path_buf);
+            (config, root)
         }
+        None => (Config::default(), cwd.to_path_buf()),
+    };
+
+    if !cli.include.is_empty() {
+        config.docs.clone_from(&cli.include);
+    }
+    if !cli.exclude.is_empty() {
+        config.exclude.clone_from(&cli.exclude);
+    }
+    if let Some(marker) = &cli.marker {
+        config.marker.clone_from(marker);
+    }
+    if cli.strict_whitespace || cli.no_strict_whitespace {
+        config.strict_whitespace = cli.strict_whitespace;
+    }
+    if cli.keep_markers || cli.no_keep_markers {
+        config.keep_markers = cli.keep_markers;
+    }
+    if let Some(language_hint) = cli.language_hint {
+        config.language_hint = language_hint;
+    }
+    if let Some(format) = cli.format {
+        config.format = format;
     }
+    Ok((config, root))
+}
 
-    discovered.sort();
+/// Determine which files to operate on, walking directories recursively and
+/// defaulting to `root`.
+fn resolve_files(
+    cli_files: &[PathBuf],
+    root: &Path,
+    config: &Config,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let roots = if cli_files.is_empty() {
+        vec![root.to_path_buf()]
+    } else {
+        cli_files.to_vec()
+    };
+
+    let options = DiscoveryOptions {
+        include: config.docs.clone(),
+        exclude: config.exclude.clone(),
+    };
+    let discovered = discover_markdown_files(&roots, &options)?;
     if discovered.is_empty() {
-        return Err(Box::new(SnipsError::NoMarkdownFiles(cwd)));
+        return Err(Box::new(SnipsError::NoMarkdownFiles(roots[0].clone())));
     }
 
     Ok(discovered)
@@ -104,74 +195,205 @@ fn main() {
 fn run() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
 
-    let mode = if cli.diff {
-        Mode::Diff
-    } else {
-        Mode::Render { check: cli.check }
+    let mode = match &cli.command {
+        Some(Command::LintSources { paths }) => Mode::LintSources {
+            paths: paths.clone(),
+        },
+        None if cli.diff => Mode::Diff,
+        None => Mode::Render { check: cli.check },
     };
 
     let cwd = env::current_dir()?;
-    let files = resolve_files(&cli.files)?;
+    let (config, root) = load_config(&cli, &cwd)?;
 
     match mode {
         Mode::Render { check } => {
-            let mut any_updated = false;
-            for path in &files {
-                let summary: RenderSummary = sync_snippets_in_file_with_summary(path, !check)?;
-                let file_updated = summary.snippets.iter().any(|s| s.updated);
-                any_updated = any_updated || file_updated;
-
-                if cli.quiet {
-                    continue;
-                }
-
-                let display_path = relative_display(path, &cwd);
-                let file_label = format!("{}", display_path.blue().bold());
-                println!("{file_label}");
-
-                if summary.snippets.is_empty() {
-                    let none = format!("{}", "(no snippets found)".bright_yellow());
-                    println!("  {none}");
-                } else {
-                    for SnippetReport { locator, updated } in summary.snippets {
-                        let marker = locator.marker();
-                        let bullet = format!("{}", "↳".cyan());
-                        let marker_display = if updated {
-                            if check {
-                                format!("{} [out of sync]", marker.red())
-                            } else {
-                                format!("{} [updated]", marker.green())
-                            }
-                        } else {
-                            format!("{}", marker.bright_white().dimmed())
-                        };
-                        println!("  {bullet} {marker_display}");
-                    }
-                }
-            }
+            let files = resolve_files(&cli.files, &root, &config)?;
+            let any_updated = render_files(&files, check, cli.quiet, &config, &cwd)?;
             if check && any_updated {
                 process::exit(1);
             }
         }
         Mode::Diff => {
-            for path in &files {
-                let diffs = diff_file(path)?;
-                if !diffs.is_empty() {
-                    for diff in diffs {
-                        let path_display = diff.path.to_string_lossy();
-                        let name_display = if let Some(name) = &diff.name {
-                            format!("{path_display}#{name}")
-                        } else {
-                            path_display.into_owned()
-                        };
-                        println!("--- {name_display}");
-                        println!("+++ {name_display}");
-                        print_diff(&diff.old_content, &diff.new_content);
-                        println!();
+            let files = resolve_files(&cli.files, &root, &config)?;
+            diff_files(&files, &config, &cwd)?;
+        }
+        Mode::LintSources { paths } => {
+            if lint_sources(&paths, &root, cli.quiet, &config, &cwd)? {
+                process::exit(1);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Render snippets in `files`, reporting progress. Returns whether any
+/// snippet was out of date.
+fn render_files(
+    files: &[PathBuf],
+    check: bool,
+    quiet: bool,
+    config: &Config,
+    cwd: &Path,
+) -> Result<bool, SnipsError> {
+    let mut any_updated = false;
+    let mut json_files = Vec::new();
+    for path in files {
+        let summary: RenderSummary = sync_snippets_with_config(path, !check, config)?;
+        let file_updated = summary.snippets.iter().any(|s| s.updated);
+        any_updated = any_updated || file_updated;
+
+        if quiet {
+            continue;
+        }
+
+        let display_path = relative_display(path, cwd);
+        if config.format == OutputFormat::Json {
+            let snippets: Vec<Value> = summary
+                .snippets
+                .iter()
+                .map(|s| {
+                    json!({
+                        "marker": s.locator.marker(),
+                        "updated": s.updated,
+                        "warning": s.warning,
+                    })
+                })
+                .collect();
+            json_files.push(json!({
+                "path": display_path,
+                "updated": file_updated,
+                "snippets": snippets,
+            }));
+            continue;
+        }
+
+        let file_label = format!("{}", display_path.blue().bold());
+        println!("{file_label}");
+
+        if summary.snippets.is_empty() {
+            let none = format!("{}", "(no snippets found)".bright_yellow());
+            println!("  {none}");
+        } else {
+            for SnippetReport {
+                locator,
+                updated,
+                warning,
+            } in summary.snippets
+            {
+                let marker = locator.marker();
+                let bullet = format!("{}", "↳".cyan());
+                let marker_display = if updated {
+                    if check {
+                        format!("{} [out of sync]", marker.red())
+                    } else {
+                        format!("{} [updated]", marker.green())
                     }
+                } else {
+                    format!("{}", marker.bright_white().dimmed())
+                };
+                println!("  {bullet} {marker_display}");
+                if let Some(warning) = warning {
+                    let label = format!("{}", "warning:".yellow().bold());
+                    eprintln!("    {label} {warning}");
                 }
             }
         }
     }
+    if config.format == OutputFormat::Json && !quiet {
+        println!("{}", json!({ "check": check, "files": json_files }));
+    }
+    Ok(any_updated)
+}
+
+/// Print diffs between embedded snippets in `files` and their sources.
+fn diff_files(files: &[PathBuf], config: &Config, cwd: &Path) -> Result<(), SnipsError> {
+    let mut json_diffs = Vec::new();
+    for path in files {
+        let diffs = diff_file_with_config(path, config)?;
+        for diff in diffs {
+            let name_display = &diff.marker;
+            if config.format == OutputFormat::Json {
+                json_diffs.push(json!({
+                    "file": relative_display(path, cwd),
+                    "marker": name_display,
+                    "old": diff.old_content,
+                    "new": diff.new_content,
+                }));
+                continue;
+            }
+            println!("--- {name_display}");
+            println!("+++ {name_display}");
+            print_diff(&diff.old_content, &diff.new_content);
+            println!();
+        }
+    }
+    if config.format == OutputFormat::Json {
+        println!("{}", json!({ "diffs": json_diffs }));
+    }
     Ok(())
 }
+
+/// Check snippet markers in the source files beneath `paths`, defaulting to
+/// `root`. Returns whether any problem was found.
+fn lint_sources(
+    paths: &[PathBuf],
+    root: &Path,
+    quiet: bool,
+    config: &Config,
+    cwd: &Path,
+) -> Result<bool, SnipsError> {
+    let roots = if paths.is_empty() {
+        vec![root.to_path_buf()]
+    } else {
+        paths.to_vec()
+    };
+    let options = DiscoveryOptions {
+        include: Vec::new(),
+        exclude: config.exclude.clone(),
+    };
+    // Files in languages without a known comment syntax would be read with
+    // every common comment style, so stray `#`, `;` or `%` lines in data files
+    // could look like markers. Such files are only checked when named.
+    let files: Vec<PathBuf> = discover_files(&roots, &options)?
+        .into_iter()
+        .filter(|path| is_known_language(path) || roots.contains(path))
+        .collect();
+
+    let mut problems = Vec::new();
+    for path in &files {
+        problems.extend(
+            lint_source(path, config)?
+                .into_iter()
+                .map(|problem| (relative_display(path, cwd), problem)),
+        );
+    }
+
+    if config.format == OutputFormat::Json {
+        if !quiet {
+            let entries: Vec<Value> = problems
+                .iter()
+                .map(|(file, problem)| json!({ "file": file, "message": problem.to_string() }))
+                .collect();
+            println!("{}", json!({ "checked": files.len(), "problems": entries }));
+        }
+    } else {
+        for (_, problem) in &problems {
+            let label = format!("{}", "error:".red().bold());
+            eprintln!("{label} {problem}");
+        }
+        if !quiet {
+            let summary = format!(
+                "{} source files checked, {} problems",
+                files.len(),
+                problems.len()
+            );
+            if problems.is_empty() {
+                println!("{}", summary.green());
+            } else {
+                println!("{}", summary.red());
+            }
+        }
+    }
+    Ok(!problems.is_empty())
+}
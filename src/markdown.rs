@@ -0,0 +1,122 @@
+use crate::error::SnipsError;
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use std::ops::Range;
+use std::path::Path;
+
+/// A heading in markdown source.
+struct Heading {
+    /// Heading level, 1 to 6.
+    level: usize,
+    /// Plain text of the heading.
+    text: String,
+    /// Byte range of the heading in the source, underline included.
+    range: Range<usize>,
+}
+
+impl Heading {
+    /// Whether `query` names this heading, by anchor slug or by its text
+    /// ignoring case.
+    fn matches(&self, query: &str) -> bool {
+        slug(&self.text) == query || self.text.eq_ignore_ascii_case(query)
+    }
+}
+
+/// The GitHub-style anchor for heading `text`: lowercase, with punctuation
+/// removed and spaces replaced by hyphens.
+fn slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Collect the ATX and setext headings of `content`, in order.
+fn headings(content: &str) -> Vec<Heading> {
+    let mut found = Vec::new();
+    let mut current: Option<Heading> = None;
+    for (event, range) in Parser::new(content).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                current = Some(Heading {
+                    level: level as usize,
+                    text: String::new(),
+                    range,
+                });
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(heading) = current.as_mut() {
+                    heading.text.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => found.extend(current.take()),
+            _ => {}
+        }
+    }
+    found
+}
+
+/// Extract the markdown under the heading named `query` in the file at
+/// `path`, up to the next heading of the same or a higher level.
+///
+/// The heading line itself is left out; subheadings are kept.
+pub(crate) fn extract_section(
+    content: &str,
+    query: &str,
+    path: &Path,
+) -> Result<String, SnipsError> {
+    let headings = headings(content);
+    let Some(pos) = headings.iter().position(|heading| heading.matches(query)) else {
+        let available: Vec<String> = headings.iter().map(|heading| slug(&heading.text)).collect();
+        return Err(SnipsError::HeadingNotFound {
+            file: path.to_path_buf(),
+            heading: query.to_string(),
+            available: if available.is_empty() {
+                "none".to_string()
+            } else {
+                available.join(", ")
+            },
+        });
+    };
+    let level = headings[pos].level;
+    let start = headings[pos].range.end;
+    let end = headings[pos + 1..]
+        .iter()
+        .find(|heading| heading.level <= level)
+        .map_or(content.len(), |heading| heading.range.start);
+    Ok(content[start..end].trim_matches('\n').to_string())
+}
+
+/// Change the level of every heading in `text` by `shift`, keeping levels
+/// between 1 and 6.
+///
+/// Setext headings are rewritten as ATX headings when their level changes.
+pub(crate) fn shift_headings(text: &str, shift: isize) -> String {
+    let mut shifted = text.to_string();
+    // Rewrite from the end so earlier ranges stay valid.
+    for heading in headings(text).iter().rev() {
+        let level = heading.level.saturating_add_signed(shift).clamp(1, 6);
+        if level == heading.level {
+            continue;
+        }
+        let source = text[heading.range.clone()].trim_end_matches(['\r', '\n']);
+        let hashes = "#".repeat(level);
+        let body = source.trim_start();
+        let replacement = if body.starts_with('#') {
+            let indent = &source[..source.len() - body.len()];
+            format!("{indent}{hashes}{}", body.trim_start_matches('#'))
+        } else {
+            // Setext: text lines followed by an underline.
+            let lines: Vec<&str> = source.lines().collect();
+            let title: Vec<&str> = lines[..lines.len() - 1].iter().map(|l| l.trim()).collect();
+            format!("{hashes} {}", title.join(" "))
+        };
+        let start = heading.range.start;
+        shifted.replace_range(start..start + source.len(), &replacement);
+    }
+    shifted
+}
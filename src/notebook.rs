@@ -0,0 +1,273 @@
+use crate::error::SnipsError;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Text stored either as one string or as a list of lines, as notebooks do.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Text {
+    /// The whole text in one string.
+    Joined(String),
+    /// The text split into lines, each keeping its newline.
+    Lines(Vec<String>),
+}
+
+impl Text {
+    /// The text as a single string.
+    fn into_string(self) -> String {
+        match self {
+            Self::Joined(text) => text,
+            Self::Lines(lines) => lines.concat(),
+        }
+    }
+}
+
+/// The parts of a notebook file that snippets use.
+#[derive(Debug, Deserialize)]
+struct RawNotebook {
+    /// Cells in document order.
+    cells: Vec<RawCell>,
+    /// Notebook-level metadata.
+    #[serde(default)]
+    metadata: RawMetadata,
+}
+
+/// Notebook metadata naming the kernel language.
+#[derive(Debug, Default, Deserialize)]
+struct RawMetadata {
+    /// The kernel the notebook was written for.
+    kernelspec: Option<RawKernelSpec>,
+    /// Language details recorded by the kernel.
+    language_info: Option<RawLanguageInfo>,
+}
+
+/// Kernel description from notebook metadata.
+#[derive(Debug, Deserialize)]
+struct RawKernelSpec {
+    /// Kernel language, such as `python`.
+    language: Option<String>,
+}
+
+/// Language details from notebook metadata.
+#[derive(Debug, Deserialize)]
+struct RawLanguageInfo {
+    /// Language name, such as `python`.
+    name: Option<String>,
+}
+
+/// A notebook cell as stored on disk.
+#[derive(Debug, Deserialize)]
+struct RawCell {
+    /// `code`, `markdown` or `raw`.
+    cell_type: String,
+    /// Cell source.
+    source: Text,
+    /// Cell metadata, including tags.
+    #[serde(default)]
+    metadata: RawCellMetadata,
+    /// Outputs of code cells.
+    #[serde(default)]
+    outputs: Vec<RawOutput>,
+}
+
+/// Cell metadata.
+#[derive(Debug, Default, Deserialize)]
+struct RawCellMetadata {
+    /// Tags attached to the cell.
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// One output of a code cell.
+#[derive(Debug, Deserialize)]
+struct RawOutput {
+    /// `stream`, `execute_result`, `display_data` or `error`.
+    output_type: String,
+    /// Text of stream outputs.
+    text: Option<Text>,
+    /// Output data keyed by MIME type.
+    #[serde(default)]
+    data: HashMap<String, Value>,
+    /// Exception name of error outputs.
+    ename: Option<String>,
+    /// Exception message of error outputs.
+    evalue: Option<String>,
+}
+
+impl RawOutput {
+    /// The plain text of the output, if it has any.
+    fn into_text(self) -> Option<String> {
+        let text = match self.output_type.as_str() {
+            "stream" => self.text?.into_string(),
+            "execute_result" | "display_data" => {
+                let plain = self.data.get("text/plain")?.clone();
+                serde_json::from_value::<Text>(plain).ok()?.into_string()
+            }
+            "error" => format!(
+                "{}: {}",
+                self.ename.unwrap_or_default(),
+                self.evalue.unwrap_or_default()
+            ),
+            _ => return None,
+        };
+        Some(text.trim_end().to_string())
+    }
+}
+
+/// A notebook cell.
+#[derive(Debug)]
+struct Cell {
+    /// Whether this is a code cell.
+    code: bool,
+    /// Cell source without trailing newlines.
+    source: String,
+    /// Tags attached to the cell.
+    tags: Vec<String>,
+    /// Text outputs of the cell.
+    outputs: Vec<String>,
+}
+
+/// A parsed Jupyter notebook.
+#[derive(Debug)]
+pub(crate) struct Notebook {
+    /// Cells in document order.
+    cells: Vec<Cell>,
+    /// Kernel language from the notebook metadata, such as `python`.
+    pub language: Option<String>,
+}
+
+/// Whether `path` names a Jupyter notebook.
+pub(crate) fn is_notebook(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ipynb"))
+}
+
+impl Notebook {
+    /// Parse the notebook JSON `content` read from `path`.
+    pub fn parse(content: &str, path: &Path) -> Result<Self, SnipsError> {
+        let raw: RawNotebook =
+            serde_json::from_str(content).map_err(|e| SnipsError::SourceParseFailed {
+                file: path.to_path_buf(),
+                message: e.to_string(),
+            })?;
+        let metadata = raw.metadata;
+        let language = metadata
+            .kernelspec
+            .and_then(|spec| spec.language)
+            .or_else(|| metadata.language_info.and_then(|info| info.name));
+        let cells = raw
+            .cells
+            .into_iter()
+            .map(|cell| Cell {
+                code: cell.cell_type == "code",
+                source: cell.source.into_string().trim_end().to_string(),
+                tags: cell.metadata.tags,
+                outputs: cell
+                    .outputs
+                    .into_iter()
+                    .filter_map(RawOutput::into_text)
+                    .filter(|text| !text.is_empty())
+                    .collect(),
+            })
+            .collect();
+        Ok(Self { cells, language })
+    }
+
+    /// Indexes of the code cells, with their sources.
+    pub fn code_cells(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.code)
+            .map(|(index, cell)| (index, cell.source.as_str()))
+    }
+
+    /// The source of every code cell, separated by blank lines.
+    pub fn code(&self) -> String {
+        let cells: Vec<usize> = self.code_cells().map(|(index, _)| index).collect();
+        self.render(&cells, false)
+    }
+
+    /// Indexes of the cells selected by a one-based `cell=` value, either a
+    /// single cell (`3`) or an inclusive range (`2-4`).
+    ///
+    /// Only code cells are numbered, so markdown and raw cells can't end up
+    /// in a code fence.
+    pub fn numbered(&self, value: &str, path: &Path) -> Result<Vec<usize>, SnipsError> {
+        let code: Vec<usize> = self.code_cells().map(|(index, _)| index).collect();
+        let bound = |n: &str| n.trim().parse::<usize>().ok();
+        let range = match value.split_once('-') {
+            Some((start, end)) => bound(start).zip(bound(end)),
+            None => bound(value).map(|n| (n, n)),
+        };
+        match range {
+            Some((start, end)) if start > 0 && start <= end && end <= code.len() => {
+                Ok(code[start - 1..end].to_vec())
+            }
+            _ => Err(SnipsError::NotebookCellNotFound {
+                file: path.to_path_buf(),
+                cell: value.to_string(),
+                cell_count: code.len(),
+            }),
+        }
+    }
+
+    /// Indexes of the code cells carrying `tag`.
+    pub fn tagged(&self, tag: &str, path: &Path) -> Result<Vec<usize>, SnipsError> {
+        let cells: Vec<usize> = self
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.code && cell.tags.iter().any(|t| t == tag))
+            .map(|(index, _)| index)
+            .collect();
+        if !cells.is_empty() {
+            return Ok(cells);
+        }
+        let mut available: Vec<&str> = Vec::new();
+        for t in self
+            .cells
+            .iter()
+            .filter(|cell| cell.code)
+            .flat_map(|cell| &cell.tags)
+        {
+            if !available.contains(&t.as_str()) {
+                available.push(t);
+            }
+        }
+        Err(SnipsError::NotebookTagNotFound {
+            file: path.to_path_buf(),
+            tag: tag.to_string(),
+            available: if available.is_empty() {
+                "none".to_string()
+            } else {
+                available.join(", ")
+            },
+        })
+    }
+
+    /// The sources of `cells`, each followed by its outputs when `outputs` is
+    /// set, separated by blank lines.
+    pub fn render(&self, cells: &[usize], outputs: bool) -> String {
+        let mut parts: Vec<&str> = Vec::new();
+        for cell in cells.iter().map(|&index| &self.cells[index]) {
+            parts.push(&cell.source);
+            if outputs {
+                parts.extend(cell.outputs.iter().map(String::as_str));
+            }
+        }
+        parts.join("\n\n")
+    }
+
+    /// The outputs of `cells`, separated by blank lines.
+    pub fn outputs(&self, cells: &[usize]) -> String {
+        cells
+            .iter()
+            .flat_map(|&index| &self.cells[index].outputs)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
@@ -0,0 +1,134 @@
+use crate::error::SnipsError;
+use crate::replace::Replacement;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+/// Options accepted on a markdown marker.
+const SUPPORTED_OPTIONS: &str = "lang, lines, dedent, title, strict, replace, shift, outputs";
+
+/// Matches one `key`, `key=value` or `key="quoted value"` option.
+static OPTION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?P<key>[\w-]+)(?:=(?:"(?P<quoted>[^"]*)"|(?P<value>[^\s"]+)))?"#).unwrap()
+});
+
+/// Matches a relative line range such as `2-5`, `3-` or `4`.
+static RANGE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?P<start>\d+)(?P<dash>-(?P<end>\d+)?)?$").unwrap());
+
+/// Per-marker settings written after the source reference, e.g.
+/// `<!-- snips: src/lib.rs#parse lang=rust dedent=false -->`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkerOptions {
+    /// Fence language, overriding detection (`lang=`).
+    pub lang: Option<String>,
+    /// One-based, inclusive line range within the selected snippet
+    /// (`lines=2-5`, `lines=3-`, `lines=4`). A missing end is open-ended.
+    pub lines: Option<(usize, Option<usize>)>,
+    /// Remove common leading indentation (`dedent=false` keeps it).
+    pub dedent: bool,
+    /// Title added to the fence info string as `title="..."` (`title=`).
+    pub title: Option<String>,
+    /// Treat whitespace-only differences as changes for this snippet (`strict`).
+    pub strict: bool,
+    /// Substitutions for this snippet (`replace="find => with"`, repeatable).
+    pub replace: Vec<Replacement>,
+    /// Levels to add to, or with a negative value remove from, markdown
+    /// headings in the snippet (`shift=1`).
+    pub shift: isize,
+    /// Include the text outputs of notebook cells (`outputs`).
+    pub outputs: bool,
+}
+
+impl Default for MarkerOptions {
+    fn default() -> Self {
+        Self {
+            lang: None,
+            lines: None,
+            dedent: true,
+            title: None,
+            strict: false,
+            replace: Vec::new(),
+            shift: 0,
+            outputs: false,
+        }
+    }
+}
+
+impl MarkerOptions {
+    /// Parse the options text of the marker on `line` of `file`.
+    pub fn parse(text: &str, file: &Path, line: usize) -> Result<Self, SnipsError> {
+        let invalid = |option: &str, reason: &str| SnipsError::InvalidMarkerOption {
+            file: file.to_path_buf(),
+            line,
+            option: option.to_string(),
+            reason: reason.to_string(),
+            supported: SUPPORTED_OPTIONS.to_string(),
+        };
+        let mut options = Self::default();
+        for caps in OPTION_RE.captures_iter(text) {
+            let option = &caps[0];
+            let value = caps.name("quoted").or_else(|| caps.name("value"));
+            let value = value.map(|m| m.as_str());
+            match (&caps["key"], value) {
+                ("lang", Some(lang)) => options.lang = Some(lang.to_string()),
+                ("title", Some(title)) => options.title = Some(title.to_string()),
+                ("lines", Some(range)) => {
+                    options.lines = Some(
+                        parse_range(range)
+                            .ok_or_else(|| invalid(option, "expected a range such as 2-5"))?,
+                    );
+                }
+                ("dedent", Some(flag)) => {
+                    options.dedent = parse_bool(flag)
+                        .ok_or_else(|| invalid(option, "expected true or false"))?;
+                }
+                ("strict", None) => options.strict = true,
+                ("strict", Some(flag)) => {
+                    options.strict = parse_bool(flag)
+                        .ok_or_else(|| invalid(option, "expected true or false"))?;
+                }
+                ("outputs", None) => options.outputs = true,
+                ("outputs", Some(flag)) => {
+                    options.outputs = parse_bool(flag)
+                        .ok_or_else(|| invalid(option, "expected true or false"))?;
+                }
+                ("replace", Some(rule)) => options.replace.push(
+                    Replacement::parse(rule)
+                        .ok_or_else(|| invalid(option, "expected a rule such as a => b"))?,
+                ),
+                ("shift", Some(shift)) => {
+                    options.shift = shift
+                        .parse()
+                        .map_err(|_| invalid(option, "expected a number such as 1 or -1"))?;
+                }
+                ("lang" | "title" | "lines" | "dedent" | "replace" | "shift", None) => {
+                    return Err(invalid(option, "missing value"));
+                }
+                _ => return Err(invalid(option, "unknown option")),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Parse a `true`/`false` option value.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse a relative line range, rejecting line zero and reversed bounds.
+fn parse_range(value: &str) -> Option<(usize, Option<usize>)> {
+    let caps = RANGE_RE.captures(value)?;
+    let start: usize = caps["start"].parse().ok()?;
+    let end = match caps.name("end") {
+        Some(end) => Some(end.as_str().parse().ok()?),
+        None if caps.name("dash").is_some() => None,
+        None => Some(start),
+    };
+    (start > 0 && end.is_none_or(|end| end >= start)).then_some((start, end))
+}
@@ -1,10 +1,16 @@
+use crate::command;
+use crate::comment;
+use crate::config::Config;
 use crate::error::SnipsError;
+use crate::options::MarkerOptions;
 use crate::snippet::{SNIPPET_ID_CHARS, SnippetRef};
 use once_cell::sync::Lazy;
-use regex::Regex;
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag};
+use regex::{Regex, escape};
 use std::fs;
 use std::io::ErrorKind;
-use std::iter::Enumerate;
+use std::iter::{self, Enumerate};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::str::Lines;
 
@@ -15,6 +21,8 @@ pub struct SnippetDiff {
     pub path: PathBuf,
     /// Optional snippet name inside the source file.
     pub name: Option<String>,
+    /// Source references of the marker, as rendered by [`SnippetLocator::marker`].
+    pub marker: String,
     /// Content currently present in the markdown file.
     pub old_content: String,
     /// Fresh content read from the source file.
@@ -26,21 +34,95 @@ pub struct SnippetDiff {
 pub struct SnippetLocator {
     /// Snippet source path relative to the markdown file.
     pub path: PathBuf,
-    /// Optional snippet name inside the source file.
+    /// Git revision to read the source file from (`path@rev`), instead of
+    /// the working tree.
+    pub rev: Option<String>,
+    /// Optional selector inside the source file: a snippet name or line range.
     pub name: Option<String>,
+    /// Further references rendered into the same fence, in order.
+    pub parts: Vec<SnippetPart>,
+    /// Command line whose output is the snippet, for `$ command` markers.
+    /// The path is empty for these.
+    pub command: Option<String>,
+    /// Options written on the marker after the source references.
+    pub options: MarkerOptions,
 }
 
 impl SnippetLocator {
-    /// Render the locator in marker form (e.g., `path/to/file#name`).
+    /// Render the locator in marker form (e.g., `path/to/file#name`), with
+    /// the references of a combined marker separated by commas.
     pub fn marker(&self) -> String {
-        let path = self.path.to_string_lossy();
-        match &self.name {
-            Some(name) => format!("{path}#{name}"),
-            None => path.into_owned(),
+        if let Some(command) = &self.command {
+            return format!("$ {command}");
+        }
+        self.references()
+            .iter()
+            .map(SnippetPart::reference)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Every source reference of the marker, in order.
+    fn references(&self) -> Vec<SnippetPart> {
+        let first = SnippetPart {
+            path: self.path.clone(),
+            rev: self.rev.clone(),
+            name: self.name.clone(),
+        };
+        iter::once(first)
+            .chain(self.parts.iter().cloned())
+            .collect()
+    }
+}
+
+/// A source reference on a marker: a path with an optional git revision and
+/// selector.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnippetPart {
+    /// Source path relative to the markdown file.
+    pub path: PathBuf,
+    /// Git revision to read the source file from, if any.
+    pub rev: Option<String>,
+    /// Optional selector inside the source file.
+    pub name: Option<String>,
+}
+
+impl SnippetPart {
+    /// Write the reference in marker form (e.g., `path/to/file@v1.0#name`).
+    fn reference(&self) -> String {
+        let mut reference = self.path.to_string_lossy().into_owned();
+        if let Some(rev) = &self.rev {
+            reference = format!("{reference}@{rev}");
+        }
+        if let Some(name) = &self.name {
+            reference = format!("{reference}#{name}");
+        }
+        reference
+    }
+
+    /// The snippet this reference selects, resolved relative to `base`.
+    fn source(&self, base: &Path) -> SnippetRef {
+        SnippetRef {
+            path: base.join(&self.path),
+            rev: self.rev.clone(),
+            name: self.name.clone(),
         }
     }
 }
 
+/// Split a `path@rev` reference into its path and git revision.
+///
+/// An `@` that starts a path component, as in `node_modules/@scope`, belongs
+/// to the path.
+fn split_revision(path: &str) -> (&str, Option<&str>) {
+    match path.rfind('@') {
+        Some(idx) if idx > 0 && idx + 1 < path.len() && !path[..idx].ends_with('/') => {
+            (&path[..idx], Some(&path[idx + 1..]))
+        }
+        _ => (path, None),
+    }
+}
+
 /// Per-snippet report for a rendered markdown file.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SnippetReport {
@@ -48,6 +130,8 @@ pub struct SnippetReport {
     pub locator: SnippetLocator,
     /// Whether this snippet's content changed during render.
     pub updated: bool,
+    /// Advisory message about the snippet, such as a drifted line range.
+    pub warning: Option<String>,
 }
 
 /// Result of rendering snippets within a single markdown file.
@@ -61,38 +145,344 @@ pub struct RenderSummary {
     pub snippets: Vec<SnippetReport>,
 }
 
-/// Regex that matches a `<!-- snips: ... -->` marker and captures indentation,
-/// source path, and optional snippet name.
-static MARKER_RE: Lazy<Regex> = Lazy::new(|| {
+/// Container prefix that may precede a marker: indentation, blockquote `>`
+/// markers and list item bullets or numbers, in any nesting.
+const CONTAINER_PREFIX: &str = r"(?:[ \t]*(?:>[ \t]?|(?:[-+*]|\d{1,9}[.)])[ \t]+))*[ \t]*";
+
+/// Matches the container prefix at the start of a line.
+static CONTAINER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&format!("^{CONTAINER_PREFIX}")).unwrap());
+
+/// Matches a `title=` attribute in a fence info string.
+static TITLE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\btitle=(?:"[^"]*"|'[^']*'|\S+)"#).unwrap());
+
+/// Pattern for a source path and optional selector (a snippet name, line
+/// range, `/start/../end/` pattern pair or `kind=value` selector), with the
+/// given group openers for the two halves: `?P<name>` to capture or `?:`.
+fn reference_pattern(path_group: &str, name_group: &str) -> String {
+    format!(
+        r"({path_group}[^#\s,]+)(?:#({name_group}/.*?/\.\./.*?/|[\w-]+=\S+?|{SNIPPET_ID_CHARS}+))?"
+    )
+}
+
+/// Matches the first reference of a comma-separated list.
+static REFERENCE_RE: Lazy<Regex> = Lazy::new(|| {
+    let reference = reference_pattern("?P<path>", "?P<name>");
+    Regex::new(&format!(r"^{reference}\s*(?:,\s*|$)")).unwrap()
+});
+
+/// Compiled patterns for the markdown markers of a keyword.
+struct MarkdownMarkers {
+    /// Matches a `<!-- keyword: ... -->` marker.
+    open: Regex,
+    /// Matches the `<!-- /keyword -->` marker closing a raw inclusion.
+    close: Regex,
+    /// The marker keyword.
+    keyword: String,
+}
+
+impl MarkdownMarkers {
+    /// Compile the marker patterns for `keyword`.
+    fn new(keyword: &str) -> Self {
+        Self {
+            open: marker_regex(keyword),
+            close: close_regex(keyword),
+            keyword: keyword.to_string(),
+        }
+    }
+
+    /// The marker closing a raw inclusion, as rendered.
+    fn close_marker(&self) -> String {
+        format!("<!-- /{} -->", self.keyword)
+    }
+}
+
+/// Build a regex that matches the `<!-- /keyword -->` marker closing a raw
+/// inclusion.
+fn close_regex(keyword: &str) -> Regex {
+    let keyword = escape(keyword);
+    Regex::new(&format!(r"^{CONTAINER_PREFIX}<!--\s*/{keyword}\s*-->\s*$")).unwrap()
+}
+
+/// Whether a marker followed by `next` opens a raw inclusion: the line after
+/// it is blank or closes the inclusion straight away.
+fn opens_raw(next: Option<&str>, close_re: &Regex) -> bool {
+    next.is_some_and(|next| strip_container(next).trim().is_empty() || close_re.is_match(next))
+}
+
+/// Build a regex that matches a `<!-- keyword: ... -->` marker and captures
+/// its container prefix and either a `$ command` or comma-separated source
+/// references with optional `key=value` options.
+fn marker_regex(keyword: &str) -> Regex {
+    let keyword = escape(keyword);
+    let reference = reference_pattern("?:", "?:");
     Regex::new(&format!(
-        r"^(?P<indent>\s*)<!--\s*snips:\s*(?P<path>[^#\s]+)(?:#(?P<name>{SNIPPET_ID_CHARS}+))?\s*-->\s*$"
+        r#"^(?P<prefix>{CONTAINER_PREFIX})<!--\s*{keyword}:\s*(?:\$\s+(?P<command>.*?\S)|(?P<refs>{reference}(?:\s*,\s*{reference})*)(?P<options>(?:\s+[\w-]+(?:=(?:"[^"]*"|[^\s"]+))?)*))\s*-->\s*$"#
     ))
     .unwrap()
-});
+}
+
+/// Split the comma-separated references of a marker into paths, revisions
+/// and selectors.
+fn parse_references(refs: &str) -> Vec<SnippetPart> {
+    let mut parsed = Vec::new();
+    let mut rest = refs;
+    while let Some(caps) = REFERENCE_RE.captures(rest) {
+        let (path, rev) = split_revision(&caps["path"]);
+        parsed.push(SnippetPart {
+            path: PathBuf::from(path),
+            rev: rev.map(str::to_string),
+            name: caps.name("name").map(|m| m.as_str().to_string()),
+        });
+        rest = &rest[caps[0].len()..];
+    }
+    parsed
+}
+
+/// Resolve every reference of `locator` relative to `base` and join them
+/// into one snippet body, returning it with the shared language hint.
+///
+/// Parts are separated by the configured separator line and optionally
+/// headed by a comment naming their file. The language hint is kept only
+/// when every part agrees on it. Command markers run their command in `base`
+/// and have no language hint.
+fn resolve_locator(
+    locator: &SnippetLocator,
+    base: &Path,
+    config: &Config,
+) -> Result<(String, Option<String>), SnipsError> {
+    if let Some(line) = &locator.command {
+        return Ok((command::run(line, base, config)?, None));
+    }
+    let references = locator.references();
+    if let [only] = references.as_slice() {
+        return only.source(base).resolve(config, &locator.options);
+    }
+    let mut bodies = Vec::new();
+    let mut langs = Vec::new();
+    for part in &references {
+        let (code, lang) = part.source(base).resolve(config, &locator.options)?;
+        let code = code.trim_end_matches('\n');
+        if config.part_headers {
+            let path = &part.path;
+            let header = comment::for_path(path).comment(&path.to_string_lossy());
+            bodies.push(format!("{header}\n{code}"));
+        } else {
+            bodies.push(code.to_string());
+        }
+        langs.push(lang);
+    }
+    let lang = langs[0]
+        .clone()
+        .filter(|lang| langs.iter().all(|other| other.as_ref() == Some(lang)));
+    let separator = format!("\n{}\n", config.part_separator);
+    Ok((bodies.join(&separator), lang))
+}
+
+/// Compare existing and freshly rendered snippet text.
+fn content_differs(old: &str, new: &str, config: &Config, options: &MarkerOptions) -> bool {
+    if config.strict_whitespace || options.strict {
+        old != new
+    } else {
+        old.trim() != new.trim()
+    }
+}
+
+/// Flag the lines of `content` on which a marker must not be recognised.
+///
+/// Markers only count where CommonMark starts a block. Lines in fenced code
+/// and inline code spans are skipped entirely. In indented code and HTML
+/// blocks only the first line may hold a marker: an indented marker and its
+/// fence parse as a single indented code block, while a marker directly
+/// below an HTML tag is part of that tag's block.
+fn ignored_lines(content: &str) -> Vec<bool> {
+    let line_starts: Vec<usize> = iter::once(0)
+        .chain(content.match_indices('\n').map(|(idx, _)| idx + 1))
+        .collect();
+    let line_of = |offset: usize| line_starts.partition_point(|&start| start <= offset) - 1;
+    let mut ignored = vec![false; line_starts.len()];
+    let mut mark = |range: Range<usize>, skip_first: bool| {
+        if range.is_empty() {
+            return;
+        }
+        let first = line_of(range.start) + usize::from(skip_first);
+        let last = line_of(range.end - 1);
+        for flag in ignored.iter_mut().take(last + 1).skip(first) {
+            *flag = true;
+        }
+    };
+    for (event, range) in Parser::new(content).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(_))) | Event::Code(_) => {
+                mark(range, false);
+            }
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Indented) | Tag::HtmlBlock) => {
+                mark(range, true);
+            }
+            _ => {}
+        }
+    }
+    ignored
+}
 
 /// Parsed representation of a snippet marker and its fenced content.
 struct ParsedSnippet {
-    /// Whitespace indentation preceding the marker.
+    /// Container prefix preceding the marker on its own line.
+    prefix: String,
+    /// Prefix continuing the marker's container on the following lines.
     indent: String,
-    /// Width of the surrounding code fence in backticks.
+    /// Whether the snippet is included unfenced, up to a closing marker.
+    raw: bool,
+    /// Character of the surrounding code fence, a backtick or tilde.
+    fence_char: char,
+    /// Width of the surrounding code fence.
     fence_len: usize,
+    /// Info string following the opening fence, trimmed.
+    info: String,
     /// Source information recovered from the marker line.
     locator: SnippetLocator,
-    /// Original snippet text found between fences.
+    /// Options exactly as written on the marker, with their leading space,
+    /// so rewriting the marker never changes them.
+    options: String,
+    /// Original snippet text found between fences, or inside a raw
+    /// inclusion without its surrounding blank lines.
     old_content: String,
 }
 
-/// Apply indentation to every non-blank line in `content`.
+/// Build a code fence of `fence_char` at least `min_len` (and three)
+/// characters long that no line of `body` could close.
+///
+/// Lines are read with [`fence_run`], the rule used to find closing fences,
+/// and the fence is made longer than the longest run found. `body` is the
+/// snippet before the container prefix is applied to it.
+fn fence_for(body: &str, fence_char: char, min_len: usize) -> String {
+    let longest = body
+        .lines()
+        .map(|line| fence_run(line, "", fence_char).0)
+        .max()
+        .unwrap_or(0);
+    iter::repeat_n(fence_char, min_len.max(3).max(longest + 1)).collect()
+}
+
+/// Update the language token of a fence info string, keeping everything else.
+///
+/// The language is the first word of `info` (`rust` in `rust,ignore` or
+/// `python title="app.py"`), or the first class inside a Pandoc-style
+/// attribute block (`.rust` in `{.rust .numberLines}`). When `lang` is
+/// `None`, or `pin` is set and the fence already names a language, `info`
+/// is returned unchanged.
+fn merge_info(info: &str, lang: Option<&str>, pin: bool) -> String {
+    let Some(lang) = lang else {
+        return info.to_string();
+    };
+    if let Some(attrs) = info.strip_prefix('{') {
+        let class = attrs
+            .split(|c: char| c.is_whitespace() || c == '}')
+            .find(|token| token.starts_with('.'));
+        return match class {
+            Some(_) if pin => info.to_string(),
+            Some(class) => info.replacen(class, &format!(".{lang}"), 1),
+            None => {
+                let attrs = attrs.trim_start();
+                let sep = if attrs.starts_with('}') { "" } else { " " };
+                format!("{{.{lang}{sep}{attrs}")
+            }
+        };
+    }
+    let end = info
+        .find(|c: char| c.is_whitespace() || c == ',' || c == '{')
+        .unwrap_or(info.len());
+    let (token, rest) = info.split_at(end);
+    if token.is_empty() || token.contains('=') {
+        // No language yet; keep any attributes after the new one.
+        return if info.is_empty() {
+            lang.to_string()
+        } else {
+            format!("{lang} {info}")
+        };
+    }
+    if pin {
+        info.to_string()
+    } else {
+        format!("{lang}{rest}")
+    }
+}
+
+/// Set the `title="..."` attribute of a fence info string, replacing any
+/// existing title.
+fn set_title(info: &str, title: &str) -> String {
+    let attr = format!("title=\"{title}\"");
+    if let Some(existing) = TITLE_RE.find(info) {
+        let mut info = info.to_string();
+        info.replace_range(existing.range(), &attr);
+        info
+    } else if info.is_empty() {
+        attr
+    } else {
+        format!("{info} {attr}")
+    }
+}
+
+/// The run of `fence_char` opening `line` inside containers continued by
+/// `indent`, and the text after it.
+///
+/// Only the marker's own continuation prefix is removed (trailing spaces
+/// may be missing on blank lines), followed by up to three spaces of
+/// indentation, so a quoted or listed fence inside a snippet body is content
+/// rather than a closing fence. Lines outside the containers have no run.
+fn fence_run<'a>(line: &'a str, indent: &str, fence_char: char) -> (usize, &'a str) {
+    let Some(inner) = line
+        .strip_prefix(indent)
+        .or_else(|| line.strip_prefix(indent.trim_end()))
+    else {
+        return (0, line);
+    };
+    let spaces = inner.len() - inner.trim_start_matches(' ').len();
+    if spaces > 3 {
+        return (0, inner);
+    }
+    let inner = &inner[spaces..];
+    let rest = inner.trim_start_matches(fence_char);
+    (inner.len() - rest.len(), rest)
+}
+
+/// Text following the container prefix of `line`.
+fn strip_container(line: &str) -> &str {
+    &line[CONTAINER_RE.find(line).map_or(0, |m| m.end())..]
+}
+
+/// Turn a marker line's container prefix into the prefix that continues the
+/// same containers on later lines, replacing list bullets and numbers with
+/// spaces of the same width.
+fn continuation_prefix(prefix: &str) -> String {
+    prefix
+        .chars()
+        .map(|c| {
+            if c == '>' || c.is_whitespace() {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect()
+}
+
+/// Apply the container prefix `indent` to every line in `content`.
+///
+/// Blank lines receive the prefix without trailing whitespace, so they stay
+/// blank outside blockquotes and keep their `>` inside them.
 fn apply_indentation(content: String, indent: &str) -> String {
     if indent.is_empty() {
         return content;
     }
 
+    let blank_indent = indent.trim_end();
     content
         .lines()
         .map(|line| {
             if line.trim().is_empty() {
-                line.to_string()
+                format!("{blank_indent}{line}")
             } else {
                 format!("{indent}{line}")
             }
@@ -110,6 +500,15 @@ pub fn sync_snippets_in_file(path: &Path, write: bool) -> Result<Option<String>,
 pub fn sync_snippets_in_file_with_summary(
     path: &Path,
     write: bool,
+) -> Result<RenderSummary, SnipsError> {
+    sync_snippets_with_config(path, write, &Config::default())
+}
+
+/// Process a single markdown file using project settings from `config`.
+pub fn sync_snippets_with_config(
+    path: &Path,
+    write: bool,
+    config: &Config,
 ) -> Result<RenderSummary, SnipsError> {
     let content = fs::read_to_string(path).map_err(|source| match source.kind() {
         ErrorKind::NotFound => SnipsError::FileNotFound {
@@ -122,7 +521,7 @@ pub fn sync_snippets_in_file_with_summary(
         },
     })?;
     let base = path.parent().unwrap_or(Path::new("."));
-    let injection = inject_snippet_content(&content, base, path)?;
+    let injection = inject_snippet_content(&content, base, path, config)?;
     let updated = injection.rendered != content;
     if write && updated {
         fs::write(path, injection.rendered.clone())?;
@@ -137,6 +536,11 @@ pub fn sync_snippets_in_file_with_summary(
 
 /// Compute diffs between snippets embedded in `path` and their sources.
 pub fn diff_file(path: &Path) -> Result<Vec<SnippetDiff>, SnipsError> {
+    diff_file_with_config(path, &Config::default())
+}
+
+/// Compute diffs for `path` using project settings from `config`.
+pub fn diff_file_with_config(path: &Path, config: &Config) -> Result<Vec<SnippetDiff>, SnipsError> {
     let content = fs::read_to_string(path).map_err(|source| match source.kind() {
         ErrorKind::NotFound => SnipsError::FileNotFound {
             file: path.to_path_buf(),
@@ -148,7 +552,7 @@ pub fn diff_file(path: &Path) -> Result<Vec<SnippetDiff>, SnipsError> {
         },
     })?;
     let base = path.parent().unwrap_or(Path::new("."));
-    compute_diffs(&content, base, path)
+    compute_diffs(&content, base, path, config)
 }
 
 /// Scan markdown content for snippet markers and compute diffs against source files.
@@ -156,26 +560,30 @@ fn compute_diffs(
     content: &str,
     base: &Path,
     file_path: &Path,
+    config: &Config,
 ) -> Result<Vec<SnippetDiff>, SnipsError> {
-    let marker_re = &MARKER_RE;
+    let markers = &MarkdownMarkers::new(&config.marker);
+    let marker_prefix = format!("<!-- {}:", config.marker);
     let mut diffs = Vec::new();
+    let ignored = ignored_lines(content);
     let mut lines = content.lines().enumerate();
 
     while let Some((idx, line)) = lines.next() {
-        if line.trim_start().starts_with("<!-- snips:") {
-            let parsed = parse_snippet_block(marker_re, file_path, idx, line, &mut lines)?;
-            let target = base.join(&parsed.locator.path);
-            let snippet = SnippetRef {
-                path: target,
-                name: parsed.locator.name.clone(),
-            };
-            let (new_content, _) = snippet.resolve()?;
+        if !ignored[idx] && strip_container(line).starts_with(&marker_prefix) {
+            let parsed = parse_snippet_block(markers, file_path, idx, line, &mut lines)?;
+            let (new_content, _) = resolve_locator(&parsed.locator, base, config)?;
 
             // Apply the same indentation to new_content as process_content does
             let new_content_with_indent = apply_indentation(new_content, &parsed.indent);
 
-            if parsed.old_content.trim() != new_content_with_indent.trim() {
+            if content_differs(
+                &parsed.old_content,
+                &new_content_with_indent,
+                config,
+                &parsed.locator.options,
+            ) {
                 diffs.push(SnippetDiff {
+                    marker: parsed.locator.marker(),
                     path: parsed.locator.path,
                     name: parsed.locator.name,
                     old_content: parsed.old_content,
@@ -192,92 +600,163 @@ fn inject_snippet_content(
     content: &str,
     base: &Path,
     file_path: &Path,
+    config: &Config,
 ) -> Result<InjectionResult, SnipsError> {
-    let marker_re = &MARKER_RE;
+    let markers = &MarkdownMarkers::new(&config.marker);
+    let marker_prefix = format!("<!-- {}:", config.marker);
     let mut out = Vec::new();
     let mut snippets = Vec::new();
+    let ignored = ignored_lines(content);
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
     let mut lines = content.lines().enumerate();
     while let Some((idx, line)) = lines.next() {
-        if line.trim_start().starts_with("<!-- snips:") {
-            let parsed = parse_snippet_block(marker_re, file_path, idx, line, &mut lines)?;
-            let target = base.join(&parsed.locator.path);
-            let snippet = SnippetRef {
-                path: target,
-                name: parsed.locator.name.clone(),
-            };
-            let (code, lang) = snippet.resolve()?;
+        if !ignored[idx] && strip_container(line).starts_with(&marker_prefix) {
+            let parsed = parse_snippet_block(markers, file_path, idx, line, &mut lines)?;
+            let options = &parsed.locator.options;
+            let (code, detected) = resolve_locator(&parsed.locator, base, config)?;
+            let lang = options.lang.clone().or(detected);
+            let prefix = parsed.prefix.as_str();
             let indent = parsed.indent.as_str();
-            let path_display = parsed.locator.path.to_string_lossy();
-            let marker = if let Some(name) = &parsed.locator.name {
-                format!("{indent}<!-- snips: {path_display}#{name} -->")
-            } else {
-                format!("{indent}<!-- snips: {path_display} -->")
-            };
-            out.push(marker);
+            let keyword = &config.marker;
+            let refs = parsed.locator.marker();
+            let written = &parsed.options;
+            out.push(format!("{prefix}<!-- {keyword}: {refs}{written} -->"));
+            if parsed.raw {
+                let rendered = apply_indentation(code, indent);
+                snippets.push(SnippetReport {
+                    locator: parsed.locator.clone(),
+                    updated: content_differs(&parsed.old_content, &rendered, config, options),
+                    warning: None,
+                });
+                // Blank lines keep the included markdown apart from the markers.
+                let blank = indent.trim_end().to_string();
+                out.push(blank.clone());
+                if !rendered.is_empty() {
+                    out.push(rendered.replace('\n', newline));
+                    out.push(blank);
+                }
+                out.push(format!("{indent}{}", markers.close_marker()));
+                continue;
+            }
 
-            let fence = "`".repeat(parsed.fence_len.max(3));
-            let lang_hint = lang.unwrap_or_default();
-            if lang_hint.is_empty() {
-                out.push(format!("{indent}{fence}"));
-            } else {
-                out.push(format!("{indent}{fence}{lang_hint}"));
+            let fence = fence_for(&code, parsed.fence_char, parsed.fence_len);
+            // An explicit `lang=` on the marker outranks a pinned fence language.
+            let pin = config.pin_language && options.lang.is_none();
+            let mut info = merge_info(&parsed.info, lang.as_deref(), pin);
+            if let Some(title) = &options.title {
+                info = set_title(&info, title);
             }
+            out.push(format!("{indent}{fence}{info}"));
             let rendered_snippet = apply_indentation(code, indent);
-            let updated = parsed.old_content.trim() != rendered_snippet.trim();
+            let updated = content_differs(&parsed.old_content, &rendered_snippet, config, options);
+            // A `lines=` option narrows the range, so drift can't be judged from the old
+            // body, and the parts of a combined marker are checked as a whole.
+            let warning = if updated && options.lines.is_none() && parsed.locator.parts.is_empty() {
+                let snippet = SnippetRef {
+                    path: base.join(&parsed.locator.path),
+                    rev: parsed.locator.rev.clone(),
+                    name: parsed.locator.name.clone(),
+                };
+                snippet.range_drift(&parsed.old_content)?
+            } else {
+                None
+            };
             snippets.push(SnippetReport {
                 locator: parsed.locator.clone(),
                 updated,
+                warning,
             });
-            out.push(rendered_snippet);
+            out.push(rendered_snippet.replace('\n', newline));
             out.push(format!("{indent}{fence}"));
         } else {
             out.push(line.to_string());
         }
     }
     Ok(InjectionResult {
-        rendered: out.join("\n") + if content.ends_with('\n') { "\n" } else { "" },
+        rendered: out.join(newline) + if content.ends_with('\n') { newline } else { "" },
         snippets,
     })
 }
 
-/// Consume a marker line and its fenced block, returning parsed details.
+/// Consume a marker line and its fenced block or raw inclusion, returning
+/// parsed details.
 fn parse_snippet_block(
-    marker_re: &Regex,
+    markers: &MarkdownMarkers,
     file_path: &Path,
     idx: usize,
     line: &str,
     lines: &mut Enumerate<Lines<'_>>,
 ) -> Result<ParsedSnippet, SnipsError> {
-    let caps = marker_re.captures(line).ok_or(SnipsError::InvalidMarker {
-        file: file_path.to_path_buf(),
-        line: idx + 1,
-        content: line.to_string(),
-    })?;
-    let indent = caps.name("indent").unwrap().as_str().to_string();
-    let src_path = caps
-        .name("path")
-        .map(|m| PathBuf::from(m.as_str()))
-        .unwrap();
-    let snippet_name = caps.name("name").map(|m| m.as_str().to_string());
+    let caps = markers
+        .open
+        .captures(line)
+        .ok_or(SnipsError::InvalidMarker {
+            file: file_path.to_path_buf(),
+            line: idx + 1,
+            content: line.to_string(),
+        })?;
+    let prefix = caps.name("prefix").unwrap().as_str().to_string();
+    let indent = continuation_prefix(&prefix);
+    let command = caps.name("command").map(|m| m.as_str().to_string());
+    let mut refs = parse_references(caps.name("refs").map_or("", |m| m.as_str())).into_iter();
+    let first = refs.next().unwrap_or_default();
+    let parts = refs.collect();
+    let written_options = caps.name("options").map_or("", |m| m.as_str());
+    let options = MarkerOptions::parse(written_options, file_path, idx + 1)?;
+    let locator = SnippetLocator {
+        path: first.path,
+        rev: first.rev,
+        name: first.name,
+        parts,
+        command,
+        options,
+    };
+
+    if opens_raw(lines.clone().next().map(|(_, next)| next), &markers.close) {
+        let old_content = parse_raw_body(markers, file_path, idx, lines)?;
+        return Ok(ParsedSnippet {
+            prefix,
+            indent,
+            raw: true,
+            fence_char: '`',
+            fence_len: 3,
+            info: String::new(),
+            locator,
+            options: written_options.to_string(),
+            old_content,
+        });
+    }
 
     let (fence_idx, fence_line) = lines.next().ok_or(SnipsError::MissingCodeFence(idx + 1))?;
-    let trimmed = fence_line.trim_start();
-    if !trimmed.starts_with("```") {
+    let trimmed = strip_container(fence_line);
+    let fence_char = match trimmed.chars().next() {
+        Some(c @ ('`' | '~')) => c,
+        _ => return Err(SnipsError::MissingCodeFence(idx + 1)),
+    };
+    let fence_len = trimmed.chars().take_while(|&c| c == fence_char).count();
+    if fence_len < 3 {
         return Err(SnipsError::MissingCodeFence(idx + 1));
     }
-    let tick_count = trimmed.chars().take_while(|&c| c == '`').count();
-    let closing = "`".repeat(tick_count);
+    let info = trimmed[fence_len..].trim().to_string();
 
     let mut old_content_lines = Vec::new();
     for (_, inner) in lines.by_ref() {
-        if inner.trim() == closing {
+        // A closing fence uses the same character and is at least as long.
+        let (run, rest) = fence_run(inner, &indent, fence_char);
+        if run >= fence_len && rest.trim().is_empty() {
             return Ok(ParsedSnippet {
+                prefix,
                 indent,
-                fence_len: tick_count,
-                locator: SnippetLocator {
-                    path: src_path,
-                    name: snippet_name,
-                },
+                raw: false,
+                fence_char,
+                fence_len,
+                info,
+                locator,
+                options: written_options.to_string(),
                 old_content: old_content_lines.join("\n"),
             });
         }
@@ -290,6 +769,48 @@ fn parse_snippet_block(
     })
 }
 
+/// Consume the body of a raw inclusion through its closing marker, returning
+/// the body without surrounding blank lines.
+///
+/// Raw inclusions nested in the body, as in an included file that has its
+/// own, are skipped over along with their closing markers.
+fn parse_raw_body(
+    markers: &MarkdownMarkers,
+    file_path: &Path,
+    idx: usize,
+    lines: &mut Enumerate<Lines<'_>>,
+) -> Result<String, SnipsError> {
+    let mut body = Vec::new();
+    let mut depth = 0;
+    while let Some((_, inner)) = lines.next() {
+        if markers.close.is_match(inner) {
+            if depth == 0 {
+                let blank = |line: &&str| strip_container(line).trim().is_empty();
+                let start = body
+                    .iter()
+                    .position(|line| !blank(line))
+                    .unwrap_or(body.len());
+                let end = body
+                    .iter()
+                    .rposition(|line| !blank(line))
+                    .map_or(start, |end| end + 1);
+                return Ok(body[start..end].join("\n"));
+            }
+            depth -= 1;
+        } else if markers.open.is_match(inner)
+            && opens_raw(lines.clone().next().map(|(_, next)| next), &markers.close)
+        {
+            depth += 1;
+        }
+        body.push(inner);
+    }
+    Err(SnipsError::UnterminatedRawBlock {
+        file: file_path.to_path_buf(),
+        start_line: idx + 1,
+        close: markers.close_marker(),
+    })
+}
+
 /// Result of injecting the latest snippet content back into markdown.
 struct InjectionResult {
     /// Final rendered markdown text.
@@ -0,0 +1,69 @@
+use crate::error::SnipsError;
+use regex::Regex;
+use serde::Deserialize;
+use std::fmt::{self, Display, Formatter};
+
+/// A text substitution applied to rendered snippets.
+///
+/// Written as `find => with` on markers and in source annotations, with the
+/// `find` side wrapped in slashes (`/sk_\w+/ => sk_...`) for a regex.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Replacement {
+    /// Text, or regex when `regex` is set, to search for.
+    pub find: String,
+    /// Replacement text. Regex rules may refer to groups as `$1` or `$name`.
+    #[serde(default)]
+    pub with: String,
+    /// Treat `find` as a regular expression.
+    #[serde(default)]
+    pub regex: bool,
+}
+
+impl Replacement {
+    /// Parse a `find => with` rule, returning `None` when it is malformed.
+    pub fn parse(rule: &str) -> Option<Self> {
+        let (find, with) = rule.split_once("=>")?;
+        let (find, with) = (find.trim(), with.trim());
+        let pattern = find
+            .strip_prefix('/')
+            .and_then(|f| f.strip_suffix('/'))
+            .filter(|f| !f.is_empty());
+        match pattern {
+            Some(pattern) => Some(Self {
+                find: pattern.to_string(),
+                with: with.to_string(),
+                regex: true,
+            }),
+            None if find.is_empty() => None,
+            None => Some(Self {
+                find: find.to_string(),
+                with: with.to_string(),
+                regex: false,
+            }),
+        }
+    }
+
+    /// Replace every occurrence in `text`.
+    pub fn apply(&self, text: &str) -> Result<String, SnipsError> {
+        if !self.regex {
+            return Ok(text.replace(&self.find, &self.with));
+        }
+        let re = Regex::new(&self.find).map_err(|source| SnipsError::InvalidPattern {
+            pattern: self.find.clone(),
+            source,
+        })?;
+        Ok(re.replace_all(text, self.with.as_str()).into_owned())
+    }
+}
+
+impl Display for Replacement {
+    /// Write the rule in `find => with` form.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.regex {
+            write!(f, "/{}/ => {}", self.find, self.with)
+        } else {
+            write!(f, "{} => {}", self.find, self.with)
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use crate::error::SnipsError;
+use proc_macro2::{LineColumn, Span};
+use std::path::Path;
+use syn::spanned::Spanned;
+use syn::{Block, ImplItem, Item, TraitItem, Type};
+
+/// A named item found while walking a Rust source file.
+struct Entry {
+    /// Item path, e.g. `Parser`, `Parser::parse`, `impl-Display-for-Parser`.
+    path: String,
+    /// Whether the item is a function or method.
+    is_fn: bool,
+    /// Span covering the item including its attributes and doc comments.
+    span: Span,
+}
+
+/// How a Rust item is looked up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ItemQuery {
+    /// An item addressed by its full path.
+    Path(String),
+    /// A function or method addressed by its name or a path suffix.
+    Function(String),
+}
+
+impl ItemQuery {
+    /// The query as written in the marker.
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Path(path) | Self::Function(path) => path,
+        }
+    }
+
+    /// Whether `entry` satisfies the query.
+    fn matches(&self, entry: &Entry) -> bool {
+        match self {
+            Self::Path(path) => entry.path == *path,
+            Self::Function(name) => {
+                entry.is_fn && (entry.path == *name || entry.path.ends_with(&format!("::{name}")))
+            }
+        }
+    }
+}
+
+/// Collects item entries and function bodies from a parsed file.
+#[derive(Default)]
+struct Collector {
+    /// All addressable items in source order.
+    entries: Vec<Entry>,
+    /// Spans of every function body, used for elision.
+    bodies: Vec<Span>,
+}
+
+impl Collector {
+    /// Record an addressable item.
+    fn push(&mut self, path: String, is_fn: bool, span: Span) {
+        self.entries.push(Entry { path, is_fn, span });
+    }
+
+    /// Record a function body.
+    fn body(&mut self, block: &Block) {
+        self.bodies.push(block.brace_token.span.join());
+    }
+
+    /// Walk a list of items, prefixing their names with `prefix`.
+    fn items(&mut self, items: &[Item], prefix: &str) {
+        for item in items {
+            self.item(item, prefix);
+        }
+    }
+
+    /// Record a single item and any items nested within it.
+    fn item(&mut self, item: &Item, prefix: &str) {
+        let span = item.span();
+        let named = |ident: &syn::Ident| format!("{prefix}{ident}");
+        match item {
+            Item::Fn(f) => {
+                self.push(named(&f.sig.ident), true, span);
+                self.body(&f.block);
+            }
+            Item::Struct(s) => self.push(named(&s.ident), false, span),
+            Item::Enum(e) => self.push(named(&e.ident), false, span),
+            Item::Union(u) => self.push(named(&u.ident), false, span),
+            Item::Type(t) => self.push(named(&t.ident), false, span),
+            Item::Const(c) => self.push(named(&c.ident), false, span),
+            Item::Static(s) => self.push(named(&s.ident), false, span),
+            Item::TraitAlias(t) => self.push(named(&t.ident), false, span),
+            Item::Macro(m) => {
+                if let Some(ident) = &m.ident {
+                    self.push(named(ident), false, span);
+                }
+            }
+            Item::Mod(m) => {
+                self.push(named(&m.ident), false, span);
+                if let Some((_, items)) = &m.content {
+                    self.items(items, &format!("{}::", named(&m.ident)));
+                }
+            }
+            Item::Trait(t) => {
+                let trait_path = named(&t.ident);
+                self.push(trait_path.clone(), false, span);
+                for inner in &t.items {
+                    self.trait_item(inner, &trait_path);
+                }
+            }
+            Item::Impl(i) => {
+                let self_name = type_name(&i.self_ty);
+                let impl_path = match &i.trait_ {
+                    Some((_, path, _)) => {
+                        let trait_name = path
+                            .segments
+                            .last()
+                            .map(|s| s.ident.to_string())
+                            .unwrap_or_default();
+                        format!("{prefix}impl-{trait_name}-for-{self_name}")
+                    }
+                    None => format!("{prefix}impl-{self_name}"),
+                };
+                self.push(impl_path, false, span);
+                let owner = format!("{prefix}{self_name}");
+                for inner in &i.items {
+                    self.impl_item(inner, &owner);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Record an item declared inside a trait.
+    fn trait_item(&mut self, item: &TraitItem, owner: &str) {
+        let span = item.span();
+        match item {
+            TraitItem::Fn(f) => {
+                self.push(format!("{owner}::{}", f.sig.ident), true, span);
+                if let Some(block) = &f.default {
+                    self.body(block);
+                }
+            }
+            TraitItem::Const(c) => self.push(format!("{owner}::{}", c.ident), false, span),
+            TraitItem::Type(t) => self.push(format!("{owner}::{}", t.ident), false, span),
+            _ => {}
+        }
+    }
+
+    /// Record an item declared inside an impl block.
+    fn impl_item(&mut self, item: &ImplItem, owner: &str) {
+        let span = item.span();
+        match item {
+            ImplItem::Fn(f) => {
+                self.push(format!("{owner}::{}", f.sig.ident), true, span);
+                self.body(&f.block);
+            }
+            ImplItem::Const(c) => self.push(format!("{owner}::{}", c.ident), false, span),
+            ImplItem::Type(t) => self.push(format!("{owner}::{}", t.ident), false, span),
+            _ => {}
+        }
+    }
+}
+
+/// Name used to address the self type of an impl block.
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(p) => p
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string())
+            .unwrap_or_default(),
+        Type::Reference(r) => type_name(&r.elem),
+        _ => String::new(),
+    }
+}
+
+/// Byte offsets of the start of each line in `content`.
+fn line_starts(content: &str) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(
+        content
+            .char_indices()
+            .filter(|&(_, c)| c == '\n')
+            .map(|(i, _)| i + 1),
+    );
+    starts
+}
+
+/// Convert a one-based line and zero-based character column to a byte offset.
+fn offset(content: &str, starts: &[usize], pos: LineColumn) -> usize {
+    let line_start = starts[pos.line - 1];
+    content[line_start..]
+        .char_indices()
+        .nth(pos.column)
+        .map_or(content.len(), |(i, _)| line_start + i)
+}
+
+/// Extract a Rust item from `content`, optionally eliding function bodies.
+///
+/// The item is returned with its attributes and doc comments, as whole
+/// source lines. When `elide` is set, every function body inside
+/// the item is replaced with `{ ... }`.
+pub(crate) fn extract_rust_item(
+    content: &str,
+    query: &ItemQuery,
+    elide: bool,
+    path: &Path,
+) -> Result<String, SnipsError> {
+    let file = syn::parse_file(content).map_err(|e| SnipsError::SourceParseFailed {
+        file: path.to_path_buf(),
+        message: format!(
+            "{} at {}:{}",
+            e,
+            e.span().start().line,
+            e.span().start().column + 1
+        ),
+    })?;
+    let mut collector = Collector::default();
+    collector.items(&file.items, "");
+
+    let Some(entry) = collector.entries.iter().find(|e| query.matches(e)) else {
+        let available: Vec<&str> = collector.entries.iter().map(|e| e.path.as_str()).collect();
+        return Err(SnipsError::ItemNotFound {
+            file: path.to_path_buf(),
+            item: query.as_str().to_string(),
+            available_items: if available.is_empty() {
+                "none".to_string()
+            } else {
+                available.join(", ")
+            },
+        });
+    };
+
+    let starts = line_starts(content);
+    let first_line = entry.span.start().line;
+    let last_line = entry.span.end().line;
+    let begin = starts[first_line - 1];
+    let end = starts
+        .get(last_line)
+        .map_or(content.len(), |&next| next - 1);
+    let mut text = content[begin..end].trim_end_matches('\r').to_string();
+
+    if elide {
+        let item_start = offset(content, &starts, entry.span.start());
+        let item_end = offset(content, &starts, entry.span.end());
+        let mut bodies: Vec<(usize, usize)> = collector
+            .bodies
+            .iter()
+            .map(|b| {
+                (
+                    offset(content, &starts, b.start()),
+                    offset(content, &starts, b.end()),
+                )
+            })
+            .filter(|&(s, e)| s >= item_start && e <= item_end)
+            .collect();
+        // Only elide outermost bodies; nested ones vanish with their parent.
+        bodies.sort_by_key(|&(s, e)| (s, usize::MAX - e));
+        let mut outermost: Vec<(usize, usize)> = Vec::new();
+        for body in bodies {
+            if outermost.last().is_none_or(|&(_, e)| body.0 >= e) {
+                outermost.push(body);
+            }
+        }
+        for (s, e) in outermost.into_iter().rev() {
+            text.replace_range(s - begin..e - begin, "{ ... }");
+        }
+    }
+
+    Ok(text)
+}
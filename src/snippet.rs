@@ -1,19 +1,147 @@
+use crate::comment::{self, CommentSyntax};
+use crate::config::{Config, LanguageHint, RegionStyle};
+use crate::data::{DataFormat, extract_data};
 use crate::error::SnipsError;
+use crate::git;
+use crate::markdown::{extract_section, shift_headings};
+use crate::notebook::{Notebook, is_notebook};
+use crate::options::MarkerOptions;
+use crate::replace::Replacement;
+use crate::rust_item::{ItemQuery, extract_rust_item};
+use crate::symbol::extract_symbol;
+use languages::Language;
 use once_cell::sync::Lazy;
-use regex::Regex;
+use regex::{Regex, escape};
 use std::fs;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
+use std::slice;
 use textwrap::dedent;
 
 /// Allowed characters for snippet identifiers.
 pub(crate) const SNIPPET_ID_CHARS: &str = r"[\w-]";
 
-/// A snippet reference made up of a source path and an optional named section.
+/// Matches a line range selector such as `L10-L25`, `L10-` or `L-L25`.
+static LINE_RANGE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^L(?P<start>\d+)?(?P<dash>-L?(?P<end>\d+)?)?$").unwrap());
+
+/// How a snippet is selected from its source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Selector {
+    /// The region between `snips-start`/`snips-end` markers with this name.
+    Named(String),
+    /// An inclusive, one-based line range. Missing bounds are open-ended.
+    Lines {
+        /// First line to include.
+        start: Option<usize>,
+        /// Last line to include.
+        end: Option<usize>,
+    },
+    /// The lines from the first match of `start` through the next match of `end`.
+    Pattern {
+        /// Regex matching the first line of the region.
+        start: String,
+        /// Regex matching the last line of the region.
+        end: String,
+    },
+    /// A Rust item located by parsing the source file.
+    RustItem {
+        /// Which item to extract.
+        query: ItemQuery,
+        /// Replace function bodies with `{ ... }`.
+        elide: bool,
+    },
+    /// A dotted symbol path located with a language-aware parser.
+    Symbol(String),
+    /// The markdown section under a heading, by anchor or text.
+    Heading(String),
+    /// A subtree of a JSON, TOML or YAML document.
+    Data {
+        /// The document format.
+        format: DataFormat,
+        /// JSON pointer or dotted key path of the subtree.
+        path: String,
+    },
+    /// Notebook cells by one-based number or inclusive range (`3`, `2-4`).
+    Cell(String),
+    /// Notebook cells carrying a tag.
+    Tag(String),
+}
+
+/// Selector kinds written as `kind=value`.
+const SELECTOR_KINDS: &str = "item, fn, sig, symbol, heading, json, toml, yaml, cell, tag";
+
+impl Selector {
+    /// Interpret the fragment following `#` in a marker.
+    pub fn parse(fragment: &str) -> Result<Self, SnipsError> {
+        if let Some(inner) = fragment.strip_prefix('/').and_then(|f| f.strip_suffix('/'))
+            && let Some((start, end)) = inner.split_once("/../")
+        {
+            return Ok(Self::Pattern {
+                start: start.to_string(),
+                end: end.to_string(),
+            });
+        }
+        if let Some((kind, value)) = fragment.split_once('=') {
+            let value = value.to_string();
+            return match kind {
+                "item" => Ok(Self::RustItem {
+                    query: ItemQuery::Path(value),
+                    elide: false,
+                }),
+                "sig" => Ok(Self::RustItem {
+                    query: ItemQuery::Path(value),
+                    elide: true,
+                }),
+                "fn" => Ok(Self::RustItem {
+                    query: ItemQuery::Function(value),
+                    elide: false,
+                }),
+                "symbol" => Ok(Self::Symbol(value)),
+                "heading" => Ok(Self::Heading(value)),
+                "json" => Ok(Self::Data {
+                    format: DataFormat::Json,
+                    path: value,
+                }),
+                "toml" => Ok(Self::Data {
+                    format: DataFormat::Toml,
+                    path: value,
+                }),
+                "yaml" => Ok(Self::Data {
+                    format: DataFormat::Yaml,
+                    path: value,
+                }),
+                "cell" => Ok(Self::Cell(value)),
+                "tag" => Ok(Self::Tag(value)),
+                _ => Err(SnipsError::UnknownSelector {
+                    selector: fragment.to_string(),
+                    supported: SELECTOR_KINDS.to_string(),
+                }),
+            };
+        }
+        if let Some(caps) = LINE_RANGE_RE.captures(fragment) {
+            let bound = |name| caps.name(name).and_then(|m| m.as_str().parse().ok());
+            let start = bound("start");
+            let end = if caps.name("dash").is_some() {
+                bound("end")
+            } else {
+                start
+            };
+            if start.is_some() || end.is_some() {
+                return Ok(Self::Lines { start, end });
+            }
+        }
+        Ok(Self::Named(fragment.to_string()))
+    }
+}
+
+/// A snippet reference made up of a source path and an optional selector.
 pub(crate) struct SnippetRef {
     /// Path to the source file that contains the snippet.
     pub path: PathBuf,
-    /// Name of the snippet within the file, if one is specified.
+    /// Git revision to read the file from instead of the working tree.
+    pub rev: Option<String>,
+    /// Selector fragment within the file (snippet name or line range), if any.
     pub name: Option<String>,
 }
 
@@ -21,9 +149,235 @@ impl SnippetRef {
     /// Read the referenced snippet content and infer a language hint.
     ///
     /// When `name` is `None`, the whole file is returned. Otherwise the
-    /// named section between `snips-start`/`snips-end` markers is extracted.
-    pub fn resolve(&self) -> Result<(String, Option<String>), SnipsError> {
-        let content = fs::read_to_string(&self.path).map_err(|source| match source.kind() {
+    /// named section between `snips-start`/`snips-end` markers, or the
+    /// requested line range, is extracted. Marker `options` then narrow the
+    /// result to a relative line range and control dedenting. Finally the
+    /// replacement rules from `config`, from `snips-replace` annotations in
+    /// the source file and from `options` are applied, in that order.
+    ///
+    /// A Jupyter notebook stands for the source of its code cells, and its
+    /// language comes from the notebook's kernel.
+    pub fn resolve(
+        &self,
+        config: &Config,
+        options: &MarkerOptions,
+    ) -> Result<(String, Option<String>), SnipsError> {
+        let content = self.read()?;
+        let ext = self.path.extension().and_then(|s| s.to_str());
+        let notebook = if is_notebook(&self.path) {
+            Some(Notebook::parse(&content, &self.path)?)
+        } else {
+            None
+        };
+        // Notebooks take their language, and so their comment syntax, from
+        // the kernel rather than from the file extension.
+        let kernel = notebook.as_ref().and_then(|nb| nb.language.as_deref());
+        let language = match &notebook {
+            Some(_) => kernel.and_then(languages::from_name),
+            None => ext.and_then(languages::from_extension),
+        };
+        let lang = ext
+            .and_then(|ext| config.languages.get(ext).cloned())
+            .or_else(|| language.and_then(|lang| language_hint(lang, config.language_hint)));
+        let syntax = match &notebook {
+            Some(_) => comment::for_name(kernel.unwrap_or_default()),
+            None => comment::for_path(&self.path),
+        };
+        let markers = SourceMarkers::new(&config.marker, &syntax, &config.regions);
+        let content = match &notebook {
+            Some(nb) => nb.code(),
+            None => content,
+        };
+        let source_rules = self.replace_annotations(&content, &markers)?;
+        let snippet = self.select(content, notebook.as_ref(), &markers, config, options)?;
+        let snippet = hide_lines(&snippet, &markers, config.elision.as_deref());
+        let snippet = if config.keep_markers {
+            snippet
+        } else {
+            strip_marker_lines(&snippet, &markers)
+        };
+        let snippet = match options.lines {
+            Some((start, end)) => self.relative_lines(&snippet, start, end)?,
+            None => snippet,
+        };
+        let mut snippet = if options.dedent {
+            dedent(&snippet)
+        } else {
+            snippet
+        };
+        if options.shift != 0 {
+            snippet = shift_headings(&snippet, options.shift);
+        }
+        for rule in config
+            .replace
+            .iter()
+            .chain(&source_rules)
+            .chain(&options.replace)
+        {
+            snippet = rule.apply(&snippet)?;
+        }
+        Ok((snippet, lang))
+    }
+
+    /// Extract the part of `content` chosen by the selector.
+    ///
+    /// For a notebook, `content` is the source of its code cells, which every
+    /// selector but `cell=` and `tag=` works on. With the `outputs` option,
+    /// cell outputs follow the cells they belong to.
+    fn select(
+        &self,
+        content: String,
+        notebook: Option<&Notebook>,
+        markers: &SourceMarkers,
+        config: &Config,
+        options: &MarkerOptions,
+    ) -> Result<String, SnipsError> {
+        let selector = self.selector()?;
+        if let Some(nb) = notebook {
+            let cells = match &selector {
+                None => Some(nb.code_cells().map(|(index, _)| index).collect()),
+                Some(Selector::Cell(value)) => Some(nb.numbered(value, &self.path)?),
+                Some(Selector::Tag(tag)) => Some(nb.tagged(tag, &self.path)?),
+                Some(_) => None,
+            };
+            if let Some(cells) = cells {
+                return Ok(nb.render(&cells, options.outputs));
+            }
+        }
+        let output_cells = match (options.outputs, notebook, &selector) {
+            (false, ..) => None,
+            (true, Some(nb), Some(Selector::Named(name))) => Some(named_cells(nb, name, markers)),
+            (true, ..) => {
+                return Err(SnipsError::OutputsUnsupported {
+                    file: self.path.clone(),
+                });
+            }
+        };
+        let snippet = match selector {
+            None => content,
+            Some(Selector::Named(name)) => extract_named_snippet(
+                &content,
+                &name,
+                &self.path,
+                markers,
+                config.elision.as_deref(),
+            )?,
+            Some(Selector::Lines { start, end }) => {
+                extract_line_range(&content, start, end, &self.path, self.fragment())?
+            }
+            Some(Selector::Pattern { start, end }) => {
+                extract_pattern_range(&content, &start, &end, &self.path)?
+            }
+            Some(Selector::RustItem { query, elide }) => {
+                extract_rust_item(&content, &query, elide, &self.path)?
+            }
+            Some(Selector::Symbol(symbol)) => extract_symbol(&content, &symbol, &self.path)?,
+            Some(Selector::Heading(heading)) => extract_section(&content, &heading, &self.path)?,
+            Some(Selector::Data { format, path }) => {
+                extract_data(&content, format, &path, &self.path)?
+            }
+            Some(Selector::Cell(_) | Selector::Tag(_)) => {
+                return Err(SnipsError::NotANotebook {
+                    file: self.path.clone(),
+                    selector: self.fragment().to_string(),
+                });
+            }
+        };
+        let outputs = notebook
+            .zip(output_cells)
+            .map(|(nb, cells)| nb.outputs(&cells))
+            .unwrap_or_default();
+        Ok(if outputs.is_empty() {
+            snippet
+        } else {
+            format!("{snippet}\n\n{outputs}")
+        })
+    }
+
+    /// Parse the `snips-replace` annotations anywhere in the source `content`.
+    fn replace_annotations(
+        &self,
+        content: &str,
+        markers: &SourceMarkers,
+    ) -> Result<Vec<Replacement>, SnipsError> {
+        content
+            .lines()
+            .enumerate()
+            .filter_map(|(idx, line)| markers.replace_rule(line).map(|rule| (idx + 1, rule)))
+            .map(|(line, rule)| {
+                Replacement::parse(rule).ok_or_else(|| SnipsError::InvalidReplaceRule {
+                    file: self.path.clone(),
+                    line,
+                    rule: rule.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Narrow extracted `snippet` to its one-based lines `start..=end`.
+    fn relative_lines(
+        &self,
+        snippet: &str,
+        start: usize,
+        end: Option<usize>,
+    ) -> Result<String, SnipsError> {
+        let lines: Vec<&str> = snippet.lines().collect();
+        let end = end.unwrap_or(lines.len());
+        if start > end || end > lines.len() {
+            return Err(SnipsError::InvalidLineRange {
+                file: self.path.clone(),
+                range: format!("lines={start}-{end}"),
+                line_count: lines.len(),
+            });
+        }
+        Ok(lines[start - 1..end].join("\n"))
+    }
+
+    /// Explain how previously rendered text drifted within a line-range
+    /// selection, if it did.
+    ///
+    /// Returns a message when the selector is a line range and `previous`
+    /// now appears at different lines of the source file, which usually
+    /// means code was added or removed above the selected region.
+    pub fn range_drift(&self, previous: &str) -> Result<Option<String>, SnipsError> {
+        let Some(Selector::Lines { start, end }) = self.selector()? else {
+            return Ok(None);
+        };
+        let mut content = self.read()?;
+        if is_notebook(&self.path) {
+            content = Notebook::parse(&content, &self.path)?.code();
+        }
+        let Some((found_start, found_end)) = locate_lines(&content, previous) else {
+            return Ok(None);
+        };
+        let line_count = content.lines().count();
+        let (start, end) = (start.unwrap_or(1), end.unwrap_or(line_count));
+        if (found_start, found_end) == (start, end) {
+            return Ok(None);
+        }
+        Ok(Some(format!(
+            "range #{} in {} drifted: previously rendered lines now at #L{found_start}-L{found_end}",
+            self.fragment(),
+            self.path.display()
+        )))
+    }
+
+    /// Parse the selector fragment, if any.
+    fn selector(&self) -> Result<Option<Selector>, SnipsError> {
+        self.name.as_deref().map(Selector::parse).transpose()
+    }
+
+    /// The selector fragment, or an empty string for whole-file references.
+    fn fragment(&self) -> &str {
+        self.name.as_deref().unwrap_or_default()
+    }
+
+    /// Read the referenced source file, from git when a revision is set.
+    fn read(&self) -> Result<String, SnipsError> {
+        if let Some(rev) = &self.rev {
+            return git::read_at_revision(&self.path, rev);
+        }
+        fs::read_to_string(&self.path).map_err(|source| match source.kind() {
             ErrorKind::NotFound => SnipsError::FileNotFound {
                 file: self.path.clone(),
                 source,
@@ -32,84 +386,506 @@ impl SnippetRef {
                 file: self.path.clone(),
                 source,
             },
+        })
+    }
+}
+
+/// Name `lang` in the given `style`.
+fn language_hint(lang: &Language, style: LanguageHint) -> Option<String> {
+    match style {
+        LanguageHint::Codemirror => lang.codemirror_mode.map(str::to_string),
+        LanguageHint::Linguist => Some(lang.name.replace(' ', "-")),
+        LanguageHint::Github => Some(
+            lang.aliases
+                .and_then(|aliases| aliases.first())
+                .map_or_else(
+                    || lang.name.to_lowercase().replace(' ', "-"),
+                    |alias| (*alias).to_string(),
+                ),
+        ),
+        LanguageHint::None => None,
+    }
+}
+
+/// Extract an inclusive, one-based line range from `content`.
+fn extract_line_range(
+    content: &str,
+    start: Option<usize>,
+    end: Option<usize>,
+    path: &Path,
+    range: &str,
+) -> Result<String, SnipsError> {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = start.unwrap_or(1);
+    let end = end.unwrap_or(lines.len());
+    if start == 0 || start > end || end > lines.len() {
+        return Err(SnipsError::InvalidLineRange {
+            file: path.to_path_buf(),
+            range: range.to_string(),
+            line_count: lines.len(),
+        });
+    }
+    Ok(lines[start - 1..end].join("\n"))
+}
+
+/// Compile a selector pattern, reporting which pattern was invalid.
+fn compile_pattern(pattern: &str) -> Result<Regex, SnipsError> {
+    Regex::new(pattern).map_err(|source| SnipsError::InvalidPattern {
+        pattern: pattern.to_string(),
+        source,
+    })
+}
+
+/// Extract the lines from the first line matching `start` through the next
+/// line matching `end`, inclusive.
+fn extract_pattern_range(
+    content: &str,
+    start: &str,
+    end: &str,
+    path: &Path,
+) -> Result<String, SnipsError> {
+    let start_re = compile_pattern(start)?;
+    let end_re = compile_pattern(end)?;
+    let lines: Vec<&str> = content.lines().collect();
+
+    let first = lines
+        .iter()
+        .position(|line| start_re.is_match(line))
+        .ok_or_else(|| SnipsError::PatternNotMatched {
+            file: path.to_path_buf(),
+            pattern: start.to_string(),
+            start_line: 1,
         })?;
-        let lang = self
-            .path
-            .extension()
-            .and_then(|s| s.to_str())
-            .and_then(|ext| languages::from_extension(ext))
-            .and_then(|lang| lang.codemirror_mode)
-            .map(|mode| mode.to_string());
-        if let Some(name) = &self.name {
-            Ok((extract_named_snippet(&content, name, &self.path)?, lang))
-        } else {
-            Ok((dedent(&content), lang))
+    let last = lines[first + 1..]
+        .iter()
+        .position(|line| end_re.is_match(line))
+        .map(|offset| first + 1 + offset)
+        .ok_or_else(|| SnipsError::PatternNotMatched {
+            file: path.to_path_buf(),
+            pattern: end.to_string(),
+            start_line: first + 2,
+        })?;
+    Ok(lines[first..=last].join("\n"))
+}
+
+/// Find the one-based line span of `content` whose lines match `needle`,
+/// ignoring leading and trailing whitespace on each line.
+fn locate_lines(content: &str, needle: &str) -> Option<(usize, usize)> {
+    let needle: Vec<&str> = needle.trim_matches('\n').lines().map(str::trim).collect();
+    if needle.is_empty() || needle.iter().all(|l| l.is_empty()) {
+        return None;
+    }
+    let lines: Vec<&str> = content.lines().map(str::trim).collect();
+    lines
+        .windows(needle.len())
+        .position(|window| window == needle.as_slice())
+        .map(|idx| (idx + 1, idx + needle.len()))
+}
+
+/// Compiled start and end marker patterns for a marker keyword.
+///
+/// Markers are only recognised when they make up an entire comment, using the
+/// comment syntax of the source file's language.
+struct SourceMarkers {
+    /// Matches a `<keyword>-start` marker and captures the snippet name.
+    start: Regex,
+    /// Matches a `<keyword>-end` marker with an optional snippet name.
+    end: Regex,
+    /// Matches a line that is only a `<keyword>-hide` comment.
+    hide_next: Regex,
+    /// Matches a line ending in a `<keyword>-hide` comment.
+    hide_trailing: Regex,
+    /// Matches a `<keyword>-hide-start` comment line.
+    hide_start: Regex,
+    /// Matches a `<keyword>-hide-end` comment line.
+    hide_end: Regex,
+    /// Matches a `<keyword>-replace: find => with` comment line.
+    replace: Regex,
+}
+
+impl SourceMarkers {
+    /// Compile the marker patterns for `keyword` and the enabled region
+    /// `styles`, recognised in comments of `syntax`.
+    fn new(keyword: &str, syntax: &CommentSyntax, styles: &[RegionStyle]) -> Self {
+        let keyword = escape(keyword);
+        let hide = format!("{keyword}-hide");
+        let mut starts = vec![format!(r"{keyword}-start:\s*({SNIPPET_ID_CHARS}+)")];
+        let mut ends = vec![format!(r"{keyword}-end(?::(?:\s*({SNIPPET_ID_CHARS}+))?)?")];
+        let mut bare_starts = Vec::new();
+        let mut bare_ends = Vec::new();
+        for style in styles {
+            let (start, end) = region_patterns(*style);
+            if *style == RegionStyle::HashRegion {
+                // C# and friends write `#region` as a directive, not a comment.
+                bare_starts.push(start.clone());
+                bare_ends.push(end.clone());
+            }
+            starts.push(start);
+            ends.push(end);
+        }
+        Self {
+            start: comment_regex(syntax, &starts, &bare_starts),
+            end: comment_regex(syntax, &ends, &bare_ends),
+            hide_next: comment_regex(syntax, slice::from_ref(&hide), &[]),
+            hide_trailing: trailing_comment_regex(syntax, &hide),
+            hide_start: comment_regex(syntax, &[format!("{hide}-start")], &[]),
+            hide_end: comment_regex(syntax, &[format!("{hide}-end")], &[]),
+            replace: comment_regex(syntax, &[format!(r"{keyword}-replace:\s*(.+?)")], &[]),
+        }
+    }
+
+    /// The snippet name of a start marker line, if `line` is one.
+    fn start_name<'a>(&self, line: &'a str) -> Option<&'a str> {
+        self.start
+            .captures(line)
+            .and_then(|caps| caps.iter().skip(1).flatten().next())
+            .map(|m| m.as_str())
+    }
+
+    /// The parsed end marker on `line`, if it is one: `Some(None)` for an
+    /// end marker without a name.
+    fn end_name<'a>(&self, line: &'a str) -> Option<Option<&'a str>> {
+        self.end
+            .captures(line)
+            .map(|caps| caps.iter().skip(1).flatten().next().map(|m| m.as_str()))
+    }
+
+    /// Whether `line` ends the open snippet called `name`, given `depth`
+    /// regions opened inside it and not yet closed, which is updated.
+    ///
+    /// As in `lint_source`, an end marker without a name closes the innermost
+    /// open region, so it only ends `name` when no nested region is open.
+    fn ends(&self, line: &str, name: &str, depth: &mut usize) -> bool {
+        if self.start_name(line).is_some() {
+            *depth += 1;
+            return false;
+        }
+        match self.end_name(line) {
+            Some(Some(end)) if end == name => true,
+            Some(_) if *depth > 0 => {
+                *depth -= 1;
+                false
+            }
+            Some(end) => end.is_none(),
+            None => false,
+        }
+    }
+
+    /// The rule of a `<keyword>-replace` annotation line, if `line` is one.
+    fn replace_rule<'a>(&self, line: &'a str) -> Option<&'a str> {
+        self.replace
+            .captures(line)
+            .and_then(|caps| caps.iter().skip(1).flatten().next())
+            .map(|m| m.as_str())
+    }
+
+    /// Whether `line` is a start or end marker.
+    fn is_marker(&self, line: &str) -> bool {
+        self.start.is_match(line) || self.end.is_match(line)
+    }
+}
+
+/// Start and end marker bodies for a region convention.
+fn region_patterns(style: RegionStyle) -> (String, String) {
+    let id = SNIPPET_ID_CHARS;
+    match style {
+        RegionStyle::HashRegion => (
+            format!(r"#region\s+({id}+)"),
+            format!(r"#endregion(?:\s+({id}+))?"),
+        ),
+        RegionStyle::Region => (
+            format!(r"region\s+({id}+)"),
+            format!(r"endregion(?:\s+({id}+))?"),
+        ),
+        RegionStyle::Asciidoc => (format!(r"tag::({id}+)\[\]"), format!(r"end::({id}+)\[\]")),
+        RegionStyle::Mdbook => (
+            format!(r"ANCHOR:\s*({id}+)"),
+            format!(r"ANCHOR_END:\s*({id}+)"),
+        ),
+    }
+}
+
+/// Build a regex matching a line that consists solely of a comment whose
+/// text matches one of `bodies`, or solely of one of the `bare` patterns.
+///
+/// Each comment form is a separate alternative, so captures in the bodies
+/// are numbered once per form.
+fn comment_regex(syntax: &CommentSyntax, bodies: &[String], bare: &[String]) -> Regex {
+    let body = format!("(?:{})", bodies.join("|"));
+    let forms: Vec<String> = syntax
+        .line
+        .iter()
+        .map(|open| format!(r"(?:{})+\s*{body}", escape(open)))
+        .chain(
+            syntax
+                .block
+                .iter()
+                .map(|(open, close)| format!(r"{}\s*{body}\s*{}", escape(open), escape(close))),
+        )
+        .chain(bare.iter().cloned())
+        .collect();
+    Regex::new(&format!(r"^\s*(?:{})\s*$", forms.join("|"))).unwrap()
+}
+
+/// Build a regex matching a line that ends with a comment whose text is `body`.
+fn trailing_comment_regex(syntax: &CommentSyntax, body: &str) -> Regex {
+    let forms: Vec<String> = syntax
+        .line
+        .iter()
+        .map(|open| format!(r"{}\s*{body}", escape(open)))
+        .chain(
+            syntax
+                .block
+                .iter()
+                .map(|(open, close)| format!(r"{}\s*{body}\s*{}", escape(open), escape(close))),
+        )
+        .collect();
+    Regex::new(&format!(r"(?:^|\s)(?:{})\s*$", forms.join("|"))).unwrap()
+}
+
+/// Drop lines hidden by `<keyword>-hide` directives from extracted text,
+/// along with the directives themselves and `<keyword>-replace` annotations.
+///
+/// With an `elision` line, each run of hidden lines is replaced by that line,
+/// indented like the directive or hidden line that started the run. An
+/// elision line already in the text, such as one separating the parts of a
+/// snippet, absorbs the runs next to it so that elisions never repeat.
+fn hide_lines(text: &str, markers: &SourceMarkers, elision: Option<&str>) -> String {
+    if !text.lines().any(|line| {
+        markers.hide_trailing.is_match(line)
+            || markers.hide_start.is_match(line)
+            || markers.replace.is_match(line)
+    }) {
+        return text.to_string();
+    }
+    let indent_of = |line: &str| line[..line.len() - line.trim_start().len()].to_string();
+    let mut kept: Vec<String> = Vec::new();
+    // Indentation of the open `hide-start` directive, if any.
+    let mut block: Option<String> = None;
+    let mut hide_next = false;
+    // Whether the last kept line is an elision line.
+    let mut elided = false;
+    for line in text.lines() {
+        if markers.hide_start.is_match(line) {
+            block = Some(indent_of(line));
+            continue;
+        }
+        if markers.hide_end.is_match(line) {
+            block = None;
+            continue;
+        }
+        if markers.replace.is_match(line) {
+            continue;
+        }
+        if markers.hide_next.is_match(line) {
+            hide_next = true;
+            continue;
+        }
+        let hidden = block.is_some() || hide_next || markers.hide_trailing.is_match(line);
+        hide_next = false;
+        if !hidden {
+            let is_elision = elision.is_some_and(|elision| line.trim() == elision.trim());
+            if !(is_elision && elided) {
+                kept.push(line.to_string());
+            }
+            elided = is_elision;
+        } else if let Some(elision) = elision
+            && !elided
+        {
+            let indent = block.clone().unwrap_or_else(|| indent_of(line));
+            kept.push(format!("{indent}{elision}"));
+            elided = true;
         }
     }
+    let trailing = if text.ends_with('\n') { "\n" } else { "" };
+    kept.join("\n") + trailing
 }
 
-/// Matches a `snips-start` marker and captures the snippet name.
-static START_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(&format!(
-        r"snips-start:\s*(?P<name>{SNIPPET_ID_CHARS}+)\s*$"
-    ))
-    .unwrap()
-});
-/// Matches a `snips-end` marker with an optional snippet name.
-static END_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(&format!(
-        r"snips-end(?::(?:\s*(?P<name>{SNIPPET_ID_CHARS}+))?)?\s*$"
-    ))
-    .unwrap()
-});
+/// Remove start and end marker lines from extracted text.
+fn strip_marker_lines(text: &str, markers: &SourceMarkers) -> String {
+    if !text.lines().any(|line| markers.is_marker(line)) {
+        return text.to_string();
+    }
+    let kept: Vec<&str> = text
+        .lines()
+        .filter(|line| !markers.is_marker(line))
+        .collect();
+    let trailing = if text.ends_with('\n') { "\n" } else { "" };
+    kept.join("\n") + trailing
+}
+
+/// Check the snippet markers in the source file at `path` for structural
+/// problems.
+///
+/// Reports snippets started again while still open, end markers without an open snippet,
+/// named end markers that match no open snippet, regions that overlap rather
+/// than nest, and snippets that are never closed. Files that are not valid
+/// UTF-8 are skipped.
+pub fn lint_source(path: &Path, config: &Config) -> Result<Vec<SnipsError>, SnipsError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(source) if source.kind() == ErrorKind::InvalidData => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(SnipsError::FileReadFailed {
+                file: path.to_path_buf(),
+                source,
+            });
+        }
+    };
+    let markers = SourceMarkers::new(&config.marker, &comment::for_path(path), &config.regions);
+    let file = || path.to_path_buf();
+    let mut problems = Vec::new();
+    // Open snippets, innermost last, with their one-based start lines.
+    let mut open: Vec<(&str, usize)> = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        let line_no = idx + 1;
+        if let Some(name) = markers.start_name(line) {
+            // Repeating a name adds another part; reopening an open one is an error.
+            if let Some(&(_, first_line)) = open.iter().find(|(open_name, _)| *open_name == name) {
+                problems.push(SnipsError::DuplicateSnippet {
+                    file: file(),
+                    name: name.to_string(),
+                    line: line_no,
+                    first_line,
+                });
+            }
+            open.push((name, line_no));
+            continue;
+        }
+        let Some(end) = markers.end_name(line) else {
+            continue;
+        };
+        let Some(&(innermost, _)) = open.last() else {
+            problems.push(SnipsError::UnmatchedEndMarker {
+                file: file(),
+                line: line_no,
+            });
+            continue;
+        };
+        match end {
+            None => {
+                open.pop();
+            }
+            Some(name) if name == innermost => {
+                open.pop();
+            }
+            Some(name) => {
+                if let Some(pos) = open.iter().rposition(|(open_name, _)| *open_name == name) {
+                    problems.push(SnipsError::OverlappingSnippets {
+                        file: file(),
+                        name: name.to_string(),
+                        inner: innermost.to_string(),
+                        line: line_no,
+                    });
+                    open.remove(pos);
+                } else {
+                    problems.push(SnipsError::MismatchedEndMarker {
+                        file: file(),
+                        expected: innermost.to_string(),
+                        found: name.to_string(),
+                        line: line_no,
+                    });
+                    open.pop();
+                }
+            }
+        }
+    }
+    problems.extend(
+        open.into_iter()
+            .map(|(name, line)| SnipsError::UnterminatedSnippet {
+                file: file(),
+                name: name.to_string(),
+                line,
+            }),
+    );
+    Ok(problems)
+}
 
 /// Collect the names of all snippets available in the provided content.
-fn find_available_snippets(content: &str) -> Vec<String> {
+fn find_available_snippets(content: &str, markers: &SourceMarkers) -> Vec<String> {
     let mut snippets = Vec::new();
     for line in content.lines() {
-        if let Some(caps) = START_RE.captures(line)
-            && let Some(name) = caps.name("name")
-        {
-            snippets.push(name.as_str().to_string());
+        if let Some(name) = markers.start_name(line) {
+            snippets.push(name.to_string());
         }
     }
     snippets
 }
 
+/// Indexes of the notebook code cells holding part of the region `name`.
+fn named_cells(notebook: &Notebook, name: &str, markers: &SourceMarkers) -> Vec<usize> {
+    let mut open = false;
+    let mut depth = 0;
+    let mut cells = Vec::new();
+    for (index, source) in notebook.code_cells() {
+        let mut touched = open;
+        for line in source.lines() {
+            if !open && markers.start_name(line) == Some(name) {
+                open = true;
+                touched = true;
+                depth = 0;
+            } else if open && markers.ends(line, name, &mut depth) {
+                open = false;
+            }
+        }
+        if touched {
+            cells.push(index);
+        }
+    }
+    cells
+}
+
 /// Extract a named snippet between matching start/end markers, respecting indentation.
-fn extract_named_snippet(content: &str, name: &str, path: &Path) -> Result<String, SnipsError> {
-    let lines = content.lines();
+///
+/// A name may mark several disjoint parts of the file. The parts are joined in
+/// order, separated by the `elision` line when one is given, indented like the
+/// start marker of the part that follows it.
+fn extract_named_snippet(
+    content: &str,
+    name: &str,
+    path: &Path,
+    markers: &SourceMarkers,
+    elision: Option<&str>,
+) -> Result<String, SnipsError> {
+    // One-based line number of the start marker of the open part.
+    let mut open: Option<usize> = None;
     let mut found = false;
+    let mut depth = 0;
     let mut snippet = Vec::new();
 
-    for line in lines {
-        if !found {
-            if START_RE
-                .captures(line)
-                .map(|c| c.name("name").map(|m| m.as_str() == name).unwrap_or(false))
-                .unwrap_or(false)
-            {
+    for (idx, line) in content.lines().enumerate() {
+        if open.is_none() {
+            if markers.start_name(line) == Some(name) {
+                if let Some(elision) = elision
+                    && found
+                {
+                    let indent = &line[..line.len() - line.trim_start().len()];
+                    snippet.push(format!("{indent}{elision}"));
+                }
+                open = Some(idx + 1);
                 found = true;
+                depth = 0;
             }
             continue;
         }
-        if END_RE.captures(line).is_some_and(|c| {
-            // End marker matches if it has no name or if the name matches our target
-            c.name("name").is_none_or(|m| m.as_str() == name)
-        }) {
-            let text = snippet.join("\n");
-            return Ok(dedent(&text));
+        if markers.ends(line, name, &mut depth) {
+            open = None;
+            continue;
         }
         snippet.push(line.to_string());
     }
 
-    if found {
-        Err(SnipsError::UnterminatedSnippet(
-            path.to_path_buf(),
-            name.to_string(),
-        ))
+    if let Some(line) = open {
+        Err(SnipsError::UnterminatedSnippet {
+            file: path.to_path_buf(),
+            name: name.to_string(),
+            line,
+        })
+    } else if found {
+        Ok(snippet.join("\n"))
     } else {
-        let available = find_available_snippets(content);
+        let available = find_available_snippets(content, markers);
         let available_display = if available.is_empty() {
             "none".to_string()
         } else {
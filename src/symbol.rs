@@ -0,0 +1,229 @@
+use crate::error::SnipsError;
+use crate::rust_item::{ItemQuery, extract_rust_item};
+use std::path::Path;
+use tree_sitter::{Language, Node, Parser};
+
+/// Languages with symbol extraction support, as named by Linguist.
+const SUPPORTED_LANGUAGES: &str = "Go, JavaScript, Python, Rust, TSX, TypeScript";
+
+/// Node kinds that define a named symbol, per grammar.
+struct Grammar {
+    /// The tree-sitter language.
+    language: Language,
+    /// Node kinds whose `name` field names a symbol.
+    definitions: &'static [&'static str],
+    /// Node kinds that wrap a definition and belong to its extent.
+    wrappers: &'static [&'static str],
+}
+
+/// Python definitions.
+const PYTHON_DEFINITIONS: &[&str] = &["class_definition", "function_definition"];
+/// JavaScript and TypeScript definitions.
+const JS_DEFINITIONS: &[&str] = &[
+    "class_declaration",
+    "abstract_class_declaration",
+    "function_declaration",
+    "generator_function_declaration",
+    "method_definition",
+    "interface_declaration",
+    "type_alias_declaration",
+    "enum_declaration",
+    "internal_module",
+    "variable_declarator",
+];
+/// Go definitions.
+const GO_DEFINITIONS: &[&str] = &["function_declaration", "method_declaration", "type_spec"];
+
+/// Look up the grammar for a Linguist language name.
+fn grammar(language: &str) -> Option<Grammar> {
+    let grammar = match language {
+        "Python" => Grammar {
+            language: tree_sitter_python::LANGUAGE.into(),
+            definitions: PYTHON_DEFINITIONS,
+            wrappers: &["decorated_definition"],
+        },
+        "JavaScript" => Grammar {
+            language: tree_sitter_javascript::LANGUAGE.into(),
+            definitions: JS_DEFINITIONS,
+            wrappers: &["export_statement", "lexical_declaration"],
+        },
+        "TypeScript" => Grammar {
+            language: tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            definitions: JS_DEFINITIONS,
+            wrappers: &["export_statement", "lexical_declaration"],
+        },
+        "TSX" => Grammar {
+            language: tree_sitter_typescript::LANGUAGE_TSX.into(),
+            definitions: JS_DEFINITIONS,
+            wrappers: &["export_statement", "lexical_declaration"],
+        },
+        "Go" => Grammar {
+            language: tree_sitter_go::LANGUAGE.into(),
+            definitions: GO_DEFINITIONS,
+            wrappers: &["type_declaration"],
+        },
+        _ => return None,
+    };
+    Some(grammar)
+}
+
+/// A symbol found in the syntax tree.
+struct Symbol {
+    /// Dotted symbol path, e.g. `Parser.parse`.
+    path: String,
+    /// First row (zero-based) of the symbol, including leading comments.
+    start_row: usize,
+    /// Last row (zero-based) of the symbol.
+    end_row: usize,
+}
+
+/// Name of the symbol defined by `node`, if it defines one.
+fn symbol_name(node: Node<'_>, source: &[u8]) -> Option<String> {
+    let text = |n: Node<'_>| n.utf8_text(source).ok().map(str::to_string);
+    if node.kind() == "variable_declarator" {
+        let value = node.child_by_field_name("value")?;
+        if !matches!(
+            value.kind(),
+            "arrow_function" | "function_expression" | "function" | "class"
+        ) {
+            return None;
+        }
+    }
+    let name = text(node.child_by_field_name("name")?)?;
+    if node.kind() == "method_declaration" {
+        // Go methods are addressed through their receiver type.
+        let receiver = node.child_by_field_name("receiver")?;
+        return Some(format!("{}.{name}", text(receiver_type(receiver)?)?));
+    }
+    Some(name)
+}
+
+/// The type name of a Go method receiver, without any pointer or type
+/// arguments: `Stack` for `(s *Stack[T])`.
+fn receiver_type(receiver: Node<'_>) -> Option<Node<'_>> {
+    let mut cursor = receiver.walk();
+    let parameter = receiver
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "parameter_declaration")?;
+    let mut ty = parameter.child_by_field_name("type")?;
+    loop {
+        ty = match ty.kind() {
+            "type_identifier" => return Some(ty),
+            "pointer_type" | "parenthesized_type" => ty.named_child(0)?,
+            "generic_type" => ty.child_by_field_name("type")?,
+            _ => return None,
+        };
+    }
+}
+
+/// Widen a definition to include wrapping nodes and preceding comments.
+fn extent(node: Node<'_>, grammar: &Grammar) -> (usize, usize) {
+    let mut outer = node;
+    while let Some(parent) = outer.parent() {
+        let mut cursor = parent.walk();
+        let sole_definition = parent
+            .named_children(&mut cursor)
+            .filter(|c| grammar.definitions.contains(&c.kind()))
+            .count()
+            <= 1;
+        if grammar.wrappers.contains(&parent.kind()) && sole_definition {
+            outer = parent;
+        } else {
+            break;
+        }
+    }
+    let mut start_row = outer.start_position().row;
+    let mut prev = outer.prev_named_sibling();
+    while let Some(comment) = prev {
+        if comment.kind() != "comment" || comment.end_position().row + 1 != start_row {
+            break;
+        }
+        start_row = comment.start_position().row;
+        prev = comment.prev_named_sibling();
+    }
+    (start_row, outer.end_position().row)
+}
+
+/// Collect symbols beneath `node`, prefixing their names with `prefix`.
+fn collect(node: Node<'_>, prefix: &str, grammar: &Grammar, source: &[u8], out: &mut Vec<Symbol>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let mut child_prefix = prefix.to_string();
+        if grammar.definitions.contains(&child.kind())
+            && let Some(name) = symbol_name(child, source)
+        {
+            let path = format!("{prefix}{name}");
+            let (start_row, end_row) = extent(child, grammar);
+            child_prefix = format!("{path}.");
+            out.push(Symbol {
+                path,
+                start_row,
+                end_row,
+            });
+        }
+        collect(child, &child_prefix, grammar, source, out);
+    }
+}
+
+/// Extract the symbol at dotted `path` (e.g. `Parser.parse`) from `content`.
+///
+/// The language is chosen from the file extension. Rust sources are handled
+/// by the Rust item parser, with `.` treated as `::`.
+pub(crate) fn extract_symbol(content: &str, path: &str, file: &Path) -> Result<String, SnipsError> {
+    let language = file
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(languages::from_extension)
+        .map(|lang| lang.name);
+    if language == Some("Rust") {
+        let query = ItemQuery::Path(path.replace('.', "::"));
+        return extract_rust_item(content, &query, false, file);
+    }
+    let Some(grammar) = language.and_then(grammar) else {
+        return Err(SnipsError::UnsupportedLanguage {
+            file: file.to_path_buf(),
+            language: language.unwrap_or("unknown").to_string(),
+            supported: SUPPORTED_LANGUAGES.to_string(),
+        });
+    };
+
+    let mut parser = Parser::new();
+    parser
+        .set_language(&grammar.language)
+        .map_err(|e| SnipsError::SourceParseFailed {
+            file: file.to_path_buf(),
+            message: e.to_string(),
+        })?;
+    let tree = parser
+        .parse(content, None)
+        .ok_or_else(|| SnipsError::SourceParseFailed {
+            file: file.to_path_buf(),
+            message: "parser produced no syntax tree".to_string(),
+        })?;
+
+    let mut symbols = Vec::new();
+    collect(
+        tree.root_node(),
+        "",
+        &grammar,
+        content.as_bytes(),
+        &mut symbols,
+    );
+
+    let Some(symbol) = symbols.iter().find(|s| s.path == path) else {
+        let available: Vec<&str> = symbols.iter().map(|s| s.path.as_str()).collect();
+        return Err(SnipsError::ItemNotFound {
+            file: file.to_path_buf(),
+            item: path.to_string(),
+            available_items: if available.is_empty() {
+                "none".to_string()
+            } else {
+                available.join(", ")
+            },
+        });
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    let end = (symbol.end_row + 1).min(lines.len());
+    Ok(lines[symbol.start_row..end].join("\n"))
+}
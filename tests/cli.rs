@@ -86,6 +86,105 @@ mod tests {
             .success();
     }
 
+    #[test]
+    fn render_walks_directory_arguments() {
+        let dir = tempfile::tempdir().unwrap();
+        let docs = dir.path().join("docs").join("guide");
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(dir.path().join("code.rs"), "fn main(){}\n").unwrap();
+        let md = docs.join("README.md");
+        support::write_marker(&md, "<!-- snips: ../../code.rs -->");
+
+        snips_cmd_in(dir.path()).args(["docs"]).assert().success();
+
+        let content = fs::read_to_string(md).unwrap();
+        assert!(content.contains("fn main(){}"));
+    }
+
+    #[test]
+    fn exclude_glob_skips_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let md = make_example(&dir);
+
+        snips_cmd_in(dir.path())
+            .args(["--include", "*.md", "--exclude", "README.md"])
+            .assert()
+            .failure();
+
+        let content = fs::read_to_string(md).unwrap();
+        assert!(content.contains("old"));
+    }
+
+    #[test]
+    fn config_file_sets_defaults_from_subdirectory() {
+        let dir = tempfile::tempdir().unwrap();
+        let md = make_example(&dir);
+        let skipped = dir.path().join("skipped.md");
+        support::write_marker(&skipped, "<!-- snips: code.rs -->");
+        fs::write(dir.path().join("snips.toml"), "docs = [\"README.md\"]\n").unwrap();
+        let sub = dir.path().join("sub");
+        fs::create_dir(&sub).unwrap();
+
+        snips_cmd_in(&sub).assert().success();
+
+        assert!(fs::read_to_string(md).unwrap().contains("fn main(){}"));
+        assert!(fs::read_to_string(skipped).unwrap().contains("old"));
+    }
+
+    #[test]
+    fn negated_flags_override_config() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("code.rs"),
+            "// snips-start: outer\n// snips-start: inner\nx();\n// snips-end: inner\n// snips-end: outer\n",
+        )
+        .unwrap();
+        let md = dir.path().join("README.md");
+        support::write_marker(&md, "<!-- snips: code.rs#outer -->");
+        fs::write(dir.path().join("snips.toml"), "keep_markers = true\n").unwrap();
+
+        snips_cmd_in(dir.path())
+            .args(["--no-keep-markers"])
+            .assert()
+            .success();
+        assert!(!fs::read_to_string(&md).unwrap().contains("snips-start"));
+
+        snips_cmd_in(dir.path())
+            .args(["--no-keep-markers", "--keep-markers"])
+            .assert()
+            .success();
+        assert!(
+            fs::read_to_string(&md)
+                .unwrap()
+                .contains("snips-start: inner")
+        );
+    }
+
+    #[test]
+    fn unknown_enum_values_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        make_example(&dir);
+        for args in [["--format", "xml"], ["--language-hint", "pygments"]] {
+            snips_cmd_in(dir.path()).args(args).assert().failure();
+        }
+    }
+
+    #[test]
+    fn json_output_format() {
+        let dir = tempfile::tempdir().unwrap();
+        make_example(&dir);
+        let output = snips_cmd_in(dir.path())
+            .args(["--check", "--format", "json"])
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        let out: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(out["check"], true);
+        assert_eq!(out["files"][0]["path"], "README.md");
+        assert_eq!(out["files"][0]["snippets"][0]["marker"], "code.rs");
+        assert_eq!(out["files"][0]["snippets"][0]["updated"], true);
+    }
+
     #[test]
     fn diff_defaults_to_markdown_files() {
         let dir = tempfile::tempdir().unwrap();
@@ -97,4 +196,47 @@ mod tests {
         let out = String::from_utf8_lossy(&output.stdout);
         assert!(out.contains("+fn main(){}"));
     }
+
+    #[test]
+    fn lint_sources_reports_problems() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("good.rs"),
+            "// snips-start: a\n// snips-end: a\n",
+        )
+        .unwrap();
+        snips_cmd_in(dir.path())
+            .args(["lint-sources"])
+            .assert()
+            .success();
+
+        fs::write(dir.path().join("bad.rs"), "// snips-end: a\n").unwrap();
+        let output = snips_cmd_in(dir.path())
+            .args(["--format", "json", "lint-sources"])
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        let out: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(out["checked"], 2);
+        assert_eq!(out["problems"][0]["file"], "bad.rs");
+
+        snips_cmd_in(dir.path())
+            .args(["lint-sources", "good.rs"])
+            .assert()
+            .success();
+    }
+
+    #[test]
+    fn lint_sources_skips_unknown_languages_unless_named() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("data.xyz"), "# snips-end: a\n").unwrap();
+        snips_cmd_in(dir.path())
+            .args(["lint-sources"])
+            .assert()
+            .success();
+        snips_cmd_in(dir.path())
+            .args(["lint-sources", "data.xyz"])
+            .assert()
+            .failure();
+    }
 }
@@ -0,0 +1,125 @@
+//! Integration tests for markers that embed command output.
+
+/// Verify allowlisting, working directories, failures and timeouts.
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, SnipsError};
+    use std::fs;
+    use support::{fence_body, marker_block, render_in, render_with};
+
+    fn allowing(commands: &[&str]) -> Config {
+        Config {
+            commands: commands.iter().map(ToString::to_string).collect(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn output_replaces_fence_body() {
+        let marker = "<!-- snips: $ printf 'hello\\nworld\\n' -->";
+        let markdown = format!("{marker}\n```console\nold\n```\n");
+        assert_eq!(
+            render_with(&[], &markdown, &allowing(&["printf"])).unwrap(),
+            format!("{marker}\n```console\nhello\nworld\n```\n")
+        );
+    }
+
+    #[test]
+    fn commands_run_beside_the_markdown_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let docs = dir.path().join("docs");
+        fs::create_dir(&docs).unwrap();
+        fs::write(docs.join("data.txt"), "from docs\n").unwrap();
+        let markdown = marker_block("<!-- snips: $ cat data.txt -->");
+        let content = render_in(&docs, &[], &markdown, &allowing(&["cat"])).unwrap();
+        assert_eq!(fence_body(&content), "from docs");
+    }
+
+    #[test]
+    fn commands_must_be_allowlisted() {
+        let markdown = marker_block("<!-- snips: $ echo hi -->");
+        for config in [Config::default(), allowing(&["echo h"])] {
+            assert!(matches!(
+                render_with(&[], &markdown, &config),
+                Err(SnipsError::CommandNotAllowed(command)) if command == "echo hi"
+            ));
+        }
+    }
+
+    #[test]
+    fn allowlist_matches_whole_arguments() {
+        let config = allowing(&["echo run", "echo 'a b'"]);
+        for (command, expected) in [("echo run -q", "run -q"), ("echo \"a b\" c", "a b c")] {
+            let markdown = marker_block(&format!("<!-- snips: $ {command} -->"));
+            let content = render_with(&[], &markdown, &config).unwrap();
+            assert_eq!(fence_body(&content), expected, "{command}");
+        }
+        for command in ["echo run-evil", "echo runner", "echo a b"] {
+            let markdown = marker_block(&format!("<!-- snips: $ {command} -->"));
+            assert!(matches!(
+                render_with(&[], &markdown, &config),
+                Err(SnipsError::CommandNotAllowed(refused)) if refused == command
+            ));
+        }
+    }
+
+    #[test]
+    fn shell_syntax_is_passed_literally() {
+        let dir = tempfile::tempdir().unwrap();
+        for (command, expected) in [
+            ("echo hi && touch PWNED", "hi && touch PWNED"),
+            ("echo hi; touch PWNED", "hi; touch PWNED"),
+            ("echo hi | tee PWNED", "hi | tee PWNED"),
+            ("echo hi > PWNED", "hi > PWNED"),
+            ("echo $(touch PWNED)", "$(touch PWNED)"),
+            ("echo `touch PWNED`", "`touch PWNED`"),
+            ("echo $HOME ~ * {a,b} %PATH% ^", "$HOME ~ * {a,b} %PATH% ^"),
+        ] {
+            let markdown = marker_block(&format!("<!-- snips: $ {command} -->"));
+            let content = render_in(dir.path(), &[], &markdown, &allowing(&["echo"])).unwrap();
+            assert_eq!(fence_body(&content), expected, "{command}");
+        }
+        assert!(!dir.path().join("PWNED").exists());
+    }
+
+    #[test]
+    fn unbalanced_quotes_are_errors() {
+        let markdown = marker_block("<!-- snips: $ echo 'hi -->");
+        assert!(matches!(
+            render_with(&[], &markdown, &allowing(&["echo"])),
+            Err(SnipsError::InvalidCommand { command, .. }) if command == "echo 'hi"
+        ));
+    }
+
+    #[test]
+    fn failures_report_stderr() {
+        let files = [("fail.sh", "echo broken >&2\nexit 3\n")];
+        let markdown = marker_block("<!-- snips: $ sh fail.sh -->");
+        match render_with(&files, &markdown, &allowing(&["sh"])) {
+            Err(SnipsError::CommandFailed { stderr, status, .. }) => {
+                assert_eq!(stderr, "broken");
+                assert!(status.contains('3'));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn slow_commands_time_out() {
+        let config = Config {
+            command_timeout: 1,
+            ..allowing(&["sleep"])
+        };
+        let markdown = marker_block("<!-- snips: $ sleep 5 -->");
+        assert!(matches!(
+            render_with(&[], &markdown, &config),
+            Err(SnipsError::CommandTimedOut { seconds: 1, .. })
+        ));
+    }
+}
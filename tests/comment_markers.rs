@@ -0,0 +1,100 @@
+//! Integration tests for comment-aware source markers.
+
+/// Verify markers are recognised per language and only inside comments.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, SnipsError};
+    use support::{fence_body, marker_block, render_with};
+
+    #[test]
+    fn block_comments_with_closers() {
+        for (file, source, expected) in [
+            (
+                "main.c",
+                "/* snips-start: demo */\nint x = 1;\n/* snips-end: demo */\n",
+                "int x = 1;",
+            ),
+            (
+                "style.css",
+                "body {}\n/* snips-start: demo */\na { color: red; }\n/* snips-end */\n",
+                "a { color: red; }",
+            ),
+            (
+                "page.html",
+                "<!-- snips-start: demo -->\n<p>hi</p>\n<!-- snips-end: demo -->\n",
+                "<p>hi</p>",
+            ),
+        ] {
+            let markdown = marker_block(&format!("<!-- snips: {file}#demo -->"));
+            let content = render_with(&[(file, source)], &markdown, &Config::default()).unwrap();
+            assert_eq!(fence_body(&content), expected, "{file}");
+        }
+    }
+
+    #[test]
+    fn language_specific_line_comments() {
+        for (file, source, expected) in [
+            (
+                "query.sql",
+                "-- snips-start: demo\nSELECT 1;\n-- snips-end: demo\n",
+                "SELECT 1;",
+            ),
+            (
+                "init.lua",
+                "--[[ snips-start: demo ]]\nlocal x = 1\n-- snips-end: demo\n",
+                "local x = 1",
+            ),
+            (
+                "app.py",
+                "# snips-start: demo\nx = 1\n# snips-end\n",
+                "x = 1",
+            ),
+        ] {
+            let markdown = marker_block(&format!("<!-- snips: {file}#demo -->"));
+            let content = render_with(&[(file, source)], &markdown, &Config::default()).unwrap();
+            assert_eq!(fence_body(&content), expected, "{file}");
+        }
+    }
+
+    #[test]
+    fn markers_outside_comments_are_ignored() {
+        let source = "let s = \"snips-start: demo\";\n// snips-start: demo\nlet a = 1;\nlet t = \"snips-end: demo\";\n// snips-end: demo\n";
+        let markdown = marker_block("<!-- snips: code.rs#demo -->");
+        let content = render_with(&[("code.rs", source)], &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "let a = 1;\nlet t = \"snips-end: demo\";"
+        );
+    }
+
+    #[test]
+    fn foreign_comment_syntax_is_not_a_marker() {
+        let files = [(
+            "code.py",
+            "// snips-start: demo\nx = 1\n// snips-end: demo\n",
+        )];
+        let markdown = marker_block("<!-- snips: code.py#demo -->");
+        match render_with(&files, &markdown, &Config::default()) {
+            Err(SnipsError::SnippetNotFound {
+                available_snippets, ..
+            }) => assert_eq!(available_snippets, "none"),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_languages_accept_common_comments() {
+        let files = [(
+            "notes.unknownext",
+            "; snips-start: demo\nvalue\n<!-- snips-end -->\n",
+        )];
+        let markdown = marker_block("<!-- snips: notes.unknownext#demo -->");
+        let content = render_with(&files, &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "value");
+    }
+}
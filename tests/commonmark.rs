@@ -0,0 +1,69 @@
+//! Integration tests for CommonMark-aware marker detection.
+
+/// Verify markers inside code and HTML blocks are left untouched.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::Config;
+    use support::render_with;
+
+    const FILES: [(&str, &str); 1] = [("code.rs", "fn main() {}")];
+
+    #[test]
+    fn markers_in_fenced_code_are_ignored() {
+        let markdown = "Example:\n\n````markdown\n<!-- snips: missing.rs#nope -->\n```rust\nexample\n```\n````\n\n<!-- snips: code.rs -->\n```rust\nold\n```\n";
+        assert_eq!(
+            render_with(&FILES, markdown, &Config::default()).unwrap(),
+            markdown.replace("```rust\nold\n```", "```rust\nfn main() {}\n```")
+        );
+    }
+
+    #[test]
+    fn markers_in_tilde_fences_are_ignored() {
+        let markdown = "~~~\n<!-- snips: missing.rs -->\n```\nexample\n```\n~~~\n";
+        assert_eq!(
+            render_with(&FILES, markdown, &Config::default()).unwrap(),
+            markdown
+        );
+    }
+
+    #[test]
+    fn markers_inside_html_blocks_are_ignored() {
+        let markdown = "<div>\n<!-- snips: missing.rs -->\n</div>\n";
+        assert_eq!(
+            render_with(&FILES, markdown, &Config::default()).unwrap(),
+            markdown
+        );
+    }
+
+    #[test]
+    fn markers_in_indented_code_are_ignored() {
+        let markdown = "Example:\n\n    some code\n    <!-- snips: missing.rs -->\n";
+        assert_eq!(
+            render_with(&FILES, markdown, &Config::default()).unwrap(),
+            markdown
+        );
+    }
+
+    #[test]
+    fn markers_in_inline_code_spans_are_ignored() {
+        let markdown = "Write ``\n    <!-- snips: missing.rs -->\n`` to include a file.\n";
+        assert_eq!(
+            render_with(&FILES, markdown, &Config::default()).unwrap(),
+            markdown
+        );
+    }
+
+    #[test]
+    fn crlf_line_endings_are_preserved() {
+        let markdown = "# Title\r\n\r\n<!-- snips: code.rs -->\r\n```rust\r\nold\r\n```\r\n";
+        assert_eq!(
+            render_with(&FILES, markdown, &Config::default()).unwrap(),
+            "# Title\r\n\r\n<!-- snips: code.rs -->\r\n```rust\r\nfn main() {}\r\n```\r\n"
+        );
+    }
+}
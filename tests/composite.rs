@@ -0,0 +1,62 @@
+//! Integration tests for markers that combine several sources in one fence.
+
+/// Verify parts are joined, separated and headed as configured.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::Config;
+    use support::{fence_body, marker_block, render_with};
+
+    const MARKER: &str = "<!-- snips: Cargo.toml#deps, src/main.rs#usage -->";
+
+    const FILES: [(&str, &str); 2] = [
+        (
+            "Cargo.toml",
+            "[package]\nname = \"demo\"\n\n# snips-start: deps\n[dependencies]\nsnips = \"1\"\n# snips-end: deps\n",
+        ),
+        (
+            "src/main.rs",
+            "fn main() {\n    // snips-start: usage\n    snips::run();\n    // snips-end: usage\n}\n",
+        ),
+    ];
+
+    #[test]
+    fn parts_share_one_fence() {
+        let content = render_with(&FILES, &marker_block(MARKER), &Config::default()).unwrap();
+        assert_eq!(content.lines().nth(1), Some("```"));
+        assert_eq!(
+            fence_body(&content),
+            "[dependencies]\nsnips = \"1\"\n\nsnips::run();"
+        );
+    }
+
+    #[test]
+    fn separator_and_headers() {
+        let config = Config {
+            part_separator: "---".to_string(),
+            part_headers: true,
+            ..Config::default()
+        };
+        let content = render_with(&FILES, &marker_block(MARKER), &config).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "# Cargo.toml\n[dependencies]\nsnips = \"1\"\n---\n// src/main.rs\nsnips::run();"
+        );
+    }
+
+    #[test]
+    fn shared_language_and_options() {
+        let marker = "<!-- snips: src/main.rs#usage,src/main.rs#L1 lang=rs -->";
+        assert_eq!(
+            render_with(&FILES, &marker_block(marker), &Config::default()).unwrap(),
+            "<!-- snips: src/main.rs#usage, src/main.rs#L1 lang=rs -->\n```rs\nsnips::run();\n\nfn main() {\n```\n"
+        );
+        let marker = "<!-- snips: src/main.rs#usage, src/main.rs#L1 -->";
+        let content = render_with(&FILES, &marker_block(marker), &Config::default()).unwrap();
+        assert!(content.contains("```rust\n"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Integration tests for project configuration.
+
+/// Verify configuration discovery and the settings it controls.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{
+        Config, OutputFormat, SnipsError, diff_file_with_config, sync_snippets_with_config,
+    };
+    use std::fs;
+    use support::{write_marker, write_marker_block};
+
+    #[test]
+    fn discovers_snips_toml_in_ancestor() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("snips.toml"),
+            "docs = [\"docs/**/*.md\"]\nmarker = \"embed\"\nformat = \"json\"\n",
+        )
+        .unwrap();
+        let nested = dir.path().join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+
+        let (file, config) = Config::discover(&nested).unwrap().unwrap();
+        assert_eq!(file, dir.path().join("snips.toml"));
+        assert_eq!(config.docs, vec!["docs/**/*.md"]);
+        assert_eq!(config.marker, "embed");
+        assert_eq!(config.format, OutputFormat::Json);
+        assert!(!config.strict_whitespace);
+    }
+
+    #[test]
+    fn discovers_cargo_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n\n[package.metadata.snips]\nstrict_whitespace = true\n",
+        )
+        .unwrap();
+
+        let (file, config) = Config::discover(dir.path()).unwrap().unwrap();
+        assert_eq!(file, dir.path().join("Cargo.toml"));
+        assert!(config.strict_whitespace);
+    }
+
+    #[test]
+    fn snips_toml_takes_precedence_over_cargo_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n\n[package.metadata.snips]\nmarker = \"cargo\"\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("snips.toml"), "marker = \"file\"\n").unwrap();
+
+        let (_, config) = Config::discover(dir.path()).unwrap().unwrap();
+        assert_eq!(config.marker, "file");
+    }
+
+    #[test]
+    fn invalid_config_reports_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snips.toml");
+        fs::write(&path, "unknown_key = 1\n").unwrap();
+        match Config::discover(dir.path()) {
+            Err(SnipsError::InvalidConfig { file, .. }) => assert_eq!(file, path),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn custom_marker_keyword() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("code.rs"),
+            "// embed-start: foo\nfn a() {}\n// embed-end\n",
+        )
+        .unwrap();
+        let md_path = dir.path().join("doc.md");
+        write_marker(&md_path, "<!-- embed: code.rs#foo -->");
+        let config = Config {
+            marker: "embed".to_string(),
+            ..Config::default()
+        };
+        sync_snippets_with_config(&md_path, true, &config).unwrap();
+        let content = fs::read_to_string(&md_path).unwrap();
+        assert!(content.contains("<!-- embed: code.rs#foo -->\n```rust\nfn a() {}\n```"));
+    }
+
+    #[test]
+    fn language_mapping_overrides_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("code.rs"), "fn a() {}\n").unwrap();
+        let md_path = dir.path().join("doc.md");
+        write_marker(&md_path, "<!-- snips: code.rs -->");
+        let mut config = Config::default();
+        config
+            .languages
+            .insert("rs".to_string(), "rs-lang".to_string());
+        sync_snippets_with_config(&md_path, true, &config).unwrap();
+        let content = fs::read_to_string(&md_path).unwrap();
+        assert!(content.contains("```rs-lang\n"));
+    }
+
+    #[test]
+    fn strict_whitespace_reports_whitespace_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("code.rs"), "fn a() {}\n").unwrap();
+        let md_path = dir.path().join("doc.md");
+        write_marker_block(
+            &md_path,
+            "<!-- snips: code.rs -->",
+            "```",
+            "fn a() {}  ",
+            "",
+        );
+
+        let lenient = diff_file_with_config(&md_path, &Config::default()).unwrap();
+        assert!(lenient.is_empty());
+
+        let strict = Config {
+            strict_whitespace: true,
+            ..Config::default()
+        };
+        let diffs = diff_file_with_config(&md_path, &strict).unwrap();
+        assert_eq!(diffs.len(), 1);
+        let summary = sync_snippets_with_config(&md_path, false, &strict).unwrap();
+        assert!(summary.snippets[0].updated);
+    }
+}
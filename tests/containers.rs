@@ -0,0 +1,90 @@
+//! Integration tests for markers inside blockquotes and list items.
+
+/// Verify container prefixes are carried onto every rendered line.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::Config;
+    use support::render_with;
+
+    const SOURCE: &str = "// snips-start: demo\nfn a() {\n\n    b();\n}\n// snips-end: demo\n";
+    const FILES: [(&str, &str); 1] = [("code.rs", SOURCE)];
+
+    #[test]
+    fn blockquote_prefix_applies_to_blank_lines() {
+        assert_eq!(
+            render_with(
+                &FILES,
+                "> Quoted:\n>\n> <!-- snips: code.rs#demo -->\n> ```rust\n> old\n> ```\n",
+                &Config::default()
+            )
+            .unwrap(),
+            "> Quoted:\n>\n> <!-- snips: code.rs#demo -->\n> ```rust\n> fn a() {\n>\n>     b();\n> }\n> ```\n"
+        );
+    }
+
+    #[test]
+    fn callout_blocks() {
+        assert_eq!(
+            render_with(
+                &FILES,
+                "> [!NOTE]\n> <!-- snips: code.rs#demo -->\n> ```\n> ```\n",
+                &Config::default()
+            )
+            .unwrap(),
+            "> [!NOTE]\n> <!-- snips: code.rs#demo -->\n> ```rust\n> fn a() {\n>\n>     b();\n> }\n> ```\n"
+        );
+    }
+
+    #[test]
+    fn marker_on_list_item_line() {
+        assert_eq!(
+            render_with(
+                &FILES,
+                "1. First step:\n2. <!-- snips: code.rs#demo -->\n   ```rust\n   ```\n",
+                &Config::default()
+            )
+            .unwrap(),
+            "1. First step:\n2. <!-- snips: code.rs#demo -->\n   ```rust\n   fn a() {\n\n       b();\n   }\n   ```\n"
+        );
+    }
+
+    #[test]
+    fn list_inside_blockquote() {
+        assert_eq!(
+            render_with(
+                &FILES,
+                "> - <!-- snips: code.rs#demo -->\n>   ```rust\n>   ```\n",
+                &Config::default()
+            )
+            .unwrap(),
+            "> - <!-- snips: code.rs#demo -->\n>   ```rust\n>   fn a() {\n>\n>       b();\n>   }\n>   ```\n"
+        );
+    }
+
+    #[test]
+    fn quoted_and_list_fences_in_snippets_are_content() {
+        // `render_with` renders twice, so a fence closed early by one of
+        // these lines shows up as content added on the second run.
+        for source in ["> ```\n> quoted\n> ```", "- ```\n- ```"] {
+            let files = [("example.md", source)];
+            let markdown = "<!-- snips: example.md -->\n```\n```\n";
+            let content = render_with(&files, markdown, &Config::default()).unwrap();
+            assert_eq!(
+                content,
+                format!("<!-- snips: example.md -->\n```gfm\n{source}\n```\n")
+            );
+            let markdown = "> <!-- snips: example.md -->\n> ```\n> ```\n";
+            let content = render_with(&files, markdown, &Config::default()).unwrap();
+            let quoted = source.replace('\n', "\n> ");
+            assert_eq!(
+                content,
+                format!("> <!-- snips: example.md -->\n> ```gfm\n> {quoted}\n> ```\n")
+            );
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Integration tests for recursive markdown discovery.
+
+/// Verify directory walking, ignore files and glob filters.
+#[cfg(test)]
+mod tests {
+    use snips::{DiscoveryOptions, SnipsError, discover_markdown_files};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::slice;
+
+    fn touch(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "text\n").unwrap();
+    }
+
+    fn relative(root: &Path, files: &[PathBuf]) -> Vec<String> {
+        files
+            .iter()
+            .map(|p| p.strip_prefix(root).unwrap().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn walks_directories_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        touch(&root.join("README.md"));
+        touch(&root.join("docs/guide.md"));
+        touch(&root.join("docs/deep/nested.markdown"));
+        touch(&root.join("src/lib.rs"));
+
+        let files =
+            discover_markdown_files(&[root.to_path_buf()], &DiscoveryOptions::default()).unwrap();
+        assert_eq!(
+            relative(root, &files),
+            vec!["README.md", "docs/deep/nested.markdown", "docs/guide.md"]
+        );
+    }
+
+    #[test]
+    fn honours_ignore_files_and_skips_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        touch(&root.join("keep.md"));
+        touch(&root.join("generated/out.md"));
+        touch(&root.join("vendor/lib.md"));
+        touch(&root.join("target/doc/index.md"));
+        touch(&root.join(".hidden/secret.md"));
+        fs::write(root.join(".gitignore"), "generated/\n").unwrap();
+        fs::write(root.join(".ignore"), "vendor/\n").unwrap();
+
+        let files =
+            discover_markdown_files(&[root.to_path_buf()], &DiscoveryOptions::default()).unwrap();
+        assert_eq!(relative(root, &files), vec!["keep.md"]);
+    }
+
+    #[test]
+    fn include_and_exclude_globs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        touch(&root.join("README.md"));
+        touch(&root.join("docs/guide.md"));
+        touch(&root.join("docs/drafts/wip.md"));
+        touch(&root.join("docs/notes.txt"));
+
+        let options = DiscoveryOptions {
+            include: vec!["docs/**".to_string()],
+            exclude: vec!["docs/drafts".to_string()],
+        };
+        let files = discover_markdown_files(&[root.to_path_buf()], &options).unwrap();
+        assert_eq!(
+            relative(root, &files),
+            vec!["docs/guide.md", "docs/notes.txt"]
+        );
+    }
+
+    #[test]
+    fn explicit_files_bypass_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let explicit = root.join("target/keep.md");
+        touch(&explicit);
+
+        let options = DiscoveryOptions {
+            include: Vec::new(),
+            exclude: vec!["**".to_string()],
+        };
+        let files = discover_markdown_files(slice::from_ref(&explicit), &options).unwrap();
+        assert_eq!(files, vec![explicit]);
+    }
+
+    #[test]
+    fn invalid_glob() {
+        let options = DiscoveryOptions {
+            include: vec!["[".to_string()],
+            exclude: Vec::new(),
+        };
+        match discover_markdown_files(&[PathBuf::from(".")], &options) {
+            Err(SnipsError::InvalidGlob { pattern, .. }) => assert_eq!(pattern, "["),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Integration tests for fence characters and fence lengths.
+
+/// Verify tilde fences and fences that grow to fit their content.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::Config;
+    use support::render_with;
+
+    const MARKDOWN_SOURCE: &str = "# Example\n\n```rust\nfn a() {}\n```\n";
+
+    #[test]
+    fn tilde_fences_are_kept() {
+        let markdown = "<!-- snips: example.md -->\n~~~\nold\n~~~\n";
+        let files = [("example.md", "fn a() {}")];
+        assert_eq!(
+            render_with(&files, markdown, &Config::default()).unwrap(),
+            "<!-- snips: example.md -->\n~~~gfm\nfn a() {}\n~~~\n"
+        );
+    }
+
+    #[test]
+    fn backtick_fence_grows_past_content() {
+        let markdown = "<!-- snips: example.md -->\n```\nold\n```\n";
+        let files = [("example.md", MARKDOWN_SOURCE)];
+        assert_eq!(
+            render_with(&files, markdown, &Config::default()).unwrap(),
+            "<!-- snips: example.md -->\n````gfm\n# Example\n\n```rust\nfn a() {}\n```\n\n````\n"
+        );
+    }
+
+    #[test]
+    fn only_possible_closing_fences_grow_the_fence() {
+        // Up to three spaces of indentation can close a fence; four make the
+        // line indented code, and a quote or bullet makes it content.
+        let markdown = "<!-- snips: example.md -->\n```\nold\n```\n";
+        for (source, fence) in [
+            ("x\n   ````", "`````"),
+            ("x\n    ````", "```"),
+            ("x\n> ````", "```"),
+            ("x\n- ````", "```"),
+        ] {
+            let files = [("example.md", source)];
+            assert_eq!(
+                render_with(&files, markdown, &Config::default()).unwrap(),
+                format!("<!-- snips: example.md -->\n{fence}gfm\n{source}\n{fence}\n"),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn tilde_fence_ignores_backtick_runs() {
+        let markdown = "<!-- snips: example.md -->\n~~~\nold\n~~~\n";
+        let files = [("example.md", MARKDOWN_SOURCE)];
+        assert_eq!(
+            render_with(&files, markdown, &Config::default()).unwrap(),
+            "<!-- snips: example.md -->\n~~~gfm\n# Example\n\n```rust\nfn a() {}\n```\n\n~~~\n"
+        );
+    }
+
+    #[test]
+    fn longer_fences_are_preserved() {
+        let markdown = "<!-- snips: example.md -->\n`````\nold\n`````\n";
+        let files = [("example.md", "fn a() {}")];
+        assert_eq!(
+            render_with(&files, markdown, &Config::default()).unwrap(),
+            "<!-- snips: example.md -->\n`````gfm\nfn a() {}\n`````\n"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Integration tests for snippets read from a git revision.
+
+/// Verify `path@rev` references read committed content.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, SnipsError};
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+    use support::{fence_body, marker_block, render_in};
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args([
+                "-c",
+                "user.name=snips",
+                "-c",
+                "user.email=snips@example.com",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    /// A repository whose `src/lib.rs` changed after the `v1.0` tag.
+    fn repository() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        fs::write(
+            dir.path().join("src/lib.rs"),
+            "// snips-start: usage\nold_api();\n// snips-end: usage\n",
+        )
+        .unwrap();
+        git(dir.path(), &["init", "-q"]);
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-qm", "v1"]);
+        git(dir.path(), &["tag", "v1.0"]);
+        fs::write(
+            dir.path().join("src/lib.rs"),
+            "// snips-start: usage\nnew_api();\n// snips-end: usage\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn reads_content_at_revision() {
+        let dir = repository();
+        let docs = dir.path().join("docs");
+        let markdown = marker_block("<!-- snips: ../src/lib.rs@v1.0#usage -->");
+        let content = render_in(&docs, &[], &markdown, &Config::default()).unwrap();
+        assert!(content.contains("```rust\n"));
+        assert_eq!(fence_body(&content), "old_api();");
+        let markdown = marker_block("<!-- snips: ../src/lib.rs#usage -->");
+        let content = render_in(&docs, &[], &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "new_api();");
+    }
+
+    #[test]
+    fn files_removed_from_working_tree() {
+        let dir = repository();
+        fs::remove_dir_all(dir.path().join("src")).unwrap();
+        let markdown = marker_block("<!-- snips: ../src/lib.rs@v1.0#usage -->");
+        let content = render_in(&dir.path().join("docs"), &[], &markdown, &Config::default());
+        assert_eq!(fence_body(&content.unwrap()), "old_api();");
+    }
+
+    #[test]
+    fn missing_revision_and_path() {
+        let dir = repository();
+        let docs = dir.path().join("docs");
+        let markdown = marker_block("<!-- snips: ../src/lib.rs@v9.9#usage -->");
+        assert!(matches!(
+            render_in(&docs, &[], &markdown, &Config::default()),
+            Err(SnipsError::GitRevisionNotFound { rev, .. }) if rev == "v9.9"
+        ));
+        let markdown = marker_block("<!-- snips: ../src/main.rs@v1.0 -->");
+        assert!(matches!(
+            render_in(&docs, &[], &markdown, &Config::default()),
+            Err(SnipsError::GitPathNotFound { rev, .. }) if rev == "v1.0"
+        ));
+    }
+
+    #[test]
+    fn revisions_are_never_options() {
+        let dir = repository();
+        let docs = dir.path().join("docs");
+        for rev in ["--output=PWNED", "-p"] {
+            let markdown = marker_block(&format!("<!-- snips: ../src/lib.rs@{rev}#usage -->"));
+            match render_in(&docs, &[], &markdown, &Config::default()) {
+                Err(SnipsError::GitFailed { message, .. }) => {
+                    assert!(message.contains("must not start with `-`"), "{message}");
+                }
+                other => panic!("unexpected {other:?}"),
+            }
+        }
+        assert!(!docs.join("PWNED").exists());
+    }
+}
@@ -0,0 +1,95 @@
+//! Integration tests for `snips-hide` directives.
+
+/// Verify hidden lines are dropped or elided in rendered snippets.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::Config;
+    use support::{fence_body, marker_block, render_with};
+
+    const SOURCE: &str = "\
+// snips-start: demo
+fn main() {
+    let config = load().unwrap(); // snips-hide
+    // snips-hide
+    let _guard = setup();
+    run(&config);
+    // snips-hide-start
+    teardown();
+    report();
+    // snips-hide-end
+}
+// snips-end: demo
+";
+    const FILES: [(&str, &str); 1] = [("main.rs", SOURCE)];
+
+    #[test]
+    fn hidden_lines_are_dropped() {
+        let markdown = marker_block("<!-- snips: main.rs#demo -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "fn main() {\n    run(&config);\n}");
+    }
+
+    #[test]
+    fn runs_of_hidden_lines_are_elided() {
+        let config = Config {
+            elision: Some("// ...".to_string()),
+            ..Config::default()
+        };
+        let markdown = marker_block("<!-- snips: main.rs#demo -->");
+        let content = render_with(&FILES, &markdown, &config).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "fn main() {\n    // ...\n    run(&config);\n    // ...\n}"
+        );
+    }
+
+    #[test]
+    fn elisions_next_to_part_separators_collapse() {
+        let source = "\
+// snips-start: demo
+fn first() {}
+fn setup() {} // snips-hide
+// snips-end: demo
+fn unrelated() {}
+// snips-start: demo
+// snips-hide
+fn teardown() {}
+fn second() {}
+// snips-end: demo
+";
+        let config = Config {
+            elision: Some("// ...".to_string()),
+            ..Config::default()
+        };
+        let markdown = marker_block("<!-- snips: main.rs#demo -->");
+        let content = render_with(&[("main.rs", source)], &markdown, &config).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "fn first() {}\n// ...\nfn second() {}"
+        );
+    }
+
+    #[test]
+    fn whole_file_inclusion_honours_directives() {
+        let source = "import os\nimport sys  # snips-hide\n\nprint(os.name)\n";
+        let markdown = marker_block("<!-- snips: app.py -->");
+        let content = render_with(&[("app.py", source)], &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "import os\n\nprint(os.name)\n");
+    }
+
+    #[test]
+    fn directive_text_must_be_the_whole_comment() {
+        let source = "let note = 1; // see snips-hide docs\n";
+        let markdown = marker_block("<!-- snips: lib.rs -->");
+        let content = render_with(&[("lib.rs", source)], &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "let note = 1; // see snips-hide docs\n"
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Integration tests for raw, unfenced inclusion of markdown.
+
+/// Verify heading sections and marked regions are included between markers.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, SnipsError};
+    use support::render_with;
+
+    const INSTALL: &str = "\
+# Project
+
+Intro.
+
+## Installation
+
+Run the installer:
+
+```sh
+cargo install demo
+```
+
+### From source
+
+Clone it.
+
+Setext Heading
+--------------
+
+<!-- snips-start: license -->
+Licensed under **MIT**.
+<!-- snips-end: license -->
+";
+    const FILES: [(&str, &str); 1] = [("INSTALL.md", INSTALL)];
+
+    #[test]
+    fn section_under_heading() {
+        let markdown = "# Readme\n\n<!-- snips: INSTALL.md#heading=installation -->\n<!-- /snips -->\n\nMore.\n";
+        assert_eq!(
+            render_with(&FILES, markdown, &Config::default()).unwrap(),
+            "# Readme\n\n<!-- snips: INSTALL.md#heading=installation -->\n\nRun the installer:\n\n```sh\ncargo install demo\n```\n\n### From source\n\nClone it.\n\n<!-- /snips -->\n\nMore.\n"
+        );
+    }
+
+    #[test]
+    fn headings_can_be_shifted() {
+        let content = render_with(
+            &FILES,
+            "<!-- snips: INSTALL.md#heading=project shift=1 -->\n\nstale\n\n<!-- /snips -->\n",
+            &Config::default(),
+        )
+        .unwrap();
+        assert!(content.contains("\n### Installation\n"));
+        assert!(content.contains("\n#### From source\n"));
+        assert!(content.contains("\n### Setext Heading\n"));
+        assert!(!content.contains("stale"));
+    }
+
+    #[test]
+    fn marked_regions_of_markdown() {
+        let markdown = "> <!-- snips: INSTALL.md#license -->\n> <!-- /snips -->\n";
+        assert_eq!(
+            render_with(&FILES, markdown, &Config::default()).unwrap(),
+            "> <!-- snips: INSTALL.md#license -->\n>\n> Licensed under **MIT**.\n>\n> <!-- /snips -->\n"
+        );
+    }
+
+    #[test]
+    fn nested_inclusions_are_skipped() {
+        let files = [
+            ("INSTALL.md", INSTALL),
+            (
+                "PART.md",
+                "## Part\n\n<!-- snips: INSTALL.md#license -->\n\nold\n\n<!-- /snips -->\n",
+            ),
+        ];
+        let content = render_with(
+            &files,
+            "<!-- snips: PART.md#heading=part -->\n<!-- /snips -->\nafter\n",
+            &Config::default(),
+        )
+        .unwrap();
+        assert!(content.ends_with("<!-- /snips -->\n\n<!-- /snips -->\nafter\n"));
+    }
+
+    #[test]
+    fn errors() {
+        let markdown = "<!-- snips: INSTALL.md#heading=usage -->\n<!-- /snips -->\n";
+        match render_with(&FILES, markdown, &Config::default()) {
+            Err(SnipsError::HeadingNotFound { available, .. }) => {
+                assert_eq!(
+                    available,
+                    "project, installation, from-source, setext-heading"
+                );
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        let markdown = "<!-- snips: INSTALL.md#license -->\n\ntext\n";
+        assert!(matches!(
+            render_with(&FILES, markdown, &Config::default()),
+            Err(SnipsError::UnterminatedRawBlock { start_line: 1, .. })
+        ));
+    }
+}
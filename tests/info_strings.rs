@@ -0,0 +1,86 @@
+//! Integration tests for preserving fence info strings.
+
+/// Verify only the language token of an info string is managed.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::Config;
+    use support::render_with;
+
+    #[test]
+    fn attributes_after_language_are_kept() {
+        for (file, opening, expected) in [
+            ("lib.rs", "```rust,ignore", "```rust,ignore"),
+            (
+                "app.py",
+                "```text title=\"app.py\"",
+                "```python title=\"app.py\"",
+            ),
+            ("lib.rs", "```c {linenos}", "```rust {linenos}"),
+        ] {
+            let markdown = format!("<!-- snips: {file} -->\n{opening}\nold\n```\n");
+            let content = render_with(&[(file, "x = 1\n")], &markdown, &Config::default()).unwrap();
+            assert_eq!(content.lines().nth(1), Some(expected), "{opening}");
+        }
+    }
+
+    #[test]
+    fn attributes_without_language_gain_one() {
+        for (file, opening, expected) in [
+            (
+                "app.py",
+                "```title=\"app.py\"",
+                "```python title=\"app.py\"",
+            ),
+            ("app.py", "```", "```python"),
+        ] {
+            let markdown = format!("<!-- snips: {file} -->\n{opening}\nold\n```\n");
+            let content = render_with(&[(file, "x = 1\n")], &markdown, &Config::default()).unwrap();
+            assert_eq!(content.lines().nth(1), Some(expected), "{opening}");
+        }
+    }
+
+    #[test]
+    fn pandoc_attribute_blocks() {
+        for (file, opening, expected) in [
+            (
+                "lib.rs",
+                "```{.text .numberLines}",
+                "```{.rust .numberLines}",
+            ),
+            ("lib.rs", "```{#listing}", "```{.rust #listing}"),
+        ] {
+            let markdown = format!("<!-- snips: {file} -->\n{opening}\nold\n```\n");
+            let content = render_with(&[(file, "x = 1\n")], &markdown, &Config::default()).unwrap();
+            assert_eq!(content.lines().nth(1), Some(expected), "{opening}");
+        }
+    }
+
+    #[test]
+    fn unknown_source_language_keeps_info() {
+        let markdown = "<!-- snips: notes.txt -->\n```console\nold\n```\n";
+        let content =
+            render_with(&[("notes.txt", "x = 1\n")], markdown, &Config::default()).unwrap();
+        assert_eq!(content.lines().nth(1), Some("```console"));
+    }
+
+    #[test]
+    fn pinned_languages_are_not_replaced() {
+        let config = Config {
+            pin_language: true,
+            ..Config::default()
+        };
+        for (file, opening, expected) in [
+            ("app.py", "```py3", "```py3"),
+            ("app.py", "```", "```python"),
+        ] {
+            let markdown = format!("<!-- snips: {file} -->\n{opening}\nold\n```\n");
+            let content = render_with(&[(file, "x = 1\n")], &markdown, &config).unwrap();
+            assert_eq!(content.lines().nth(1), Some(expected), "{opening}");
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Integration tests for fence language hints.
+
+/// Verify hint styles, extension overrides and `lang=` on markers.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, LanguageHint};
+    use std::collections::BTreeMap;
+    use std::fs;
+    use support::{marker_block, render_with};
+
+    const FILES: [(&str, &str); 1] = [("main.cpp", "x")];
+
+    #[test]
+    fn hint_styles() {
+        for (file, style, expected) in [
+            ("main.cpp", LanguageHint::Codemirror, "```clike"),
+            ("main.cpp", LanguageHint::Linguist, "```C++"),
+            ("main.cpp", LanguageHint::Github, "```cpp"),
+            ("init.el", LanguageHint::Github, "```elisp"),
+            ("api.apib", LanguageHint::Github, "```api-blueprint"),
+            ("main.cpp", LanguageHint::None, "```"),
+        ] {
+            let config = Config {
+                language_hint: style,
+                ..Config::default()
+            };
+            let markdown = marker_block(&format!("<!-- snips: {file} -->"));
+            let content = render_with(&[(file, "x")], &markdown, &config).unwrap();
+            assert_eq!(content.lines().nth(1), Some(expected), "{file} {style:?}");
+        }
+    }
+
+    #[test]
+    fn extension_overrides_win_over_style() {
+        let config = Config {
+            language_hint: LanguageHint::Github,
+            languages: BTreeMap::from([("cpp".to_string(), "c++".to_string())]),
+            ..Config::default()
+        };
+        let markdown = marker_block("<!-- snips: main.cpp -->");
+        let content = render_with(&FILES, &markdown, &config).unwrap();
+        assert_eq!(content.lines().nth(1), Some("```c++"));
+    }
+
+    #[test]
+    fn marker_lang_override_is_kept() {
+        let config = Config {
+            pin_language: true,
+            ..Config::default()
+        };
+        let marker = "<!-- snips: main.cpp lang=cpp -->";
+        assert_eq!(
+            render_with(&FILES, &marker_block(marker), &config).unwrap(),
+            format!("{marker}\n```cpp\nx\n```\n")
+        );
+        let marker = "<!-- snips: main.cpp#L1 lang=c++ -->";
+        assert_eq!(
+            render_with(&FILES, &marker_block(marker), &Config::default()).unwrap(),
+            format!("{marker}\n```c++\nx\n```\n")
+        );
+    }
+
+    #[test]
+    fn style_from_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snips.toml");
+        fs::write(&path, "language_hint = \"github\"\n").unwrap();
+        assert_eq!(
+            Config::from_file(&path).unwrap().language_hint,
+            LanguageHint::Github
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Integration tests for line-range snippet selection.
+
+/// Verify `#L<start>-L<end>` selectors and range drift detection.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, SnipsError, sync_snippets_in_file, sync_snippets_in_file_with_summary};
+    use std::fs;
+    use support::{marker_block, render_in, render_with, write_marker};
+
+    const SOURCE: &str = "line one\n    line two\n    line three\n        line four\nline five\n";
+    const FILES: [(&str, &str); 1] = [("code.txt", SOURCE)];
+
+    #[test]
+    fn closed_range_is_dedented() {
+        let content = render_with(
+            &FILES,
+            &marker_block("<!-- snips: code.txt#L2-L4 -->"),
+            &Config::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            content,
+            "<!-- snips: code.txt#L2-L4 -->\n```\nline two\nline three\n    line four\n```\n"
+        );
+    }
+
+    #[test]
+    fn single_line() {
+        let content = render_with(
+            &FILES,
+            &marker_block("<!-- snips: code.txt#L5 -->"),
+            &Config::default(),
+        )
+        .unwrap();
+        assert!(content.contains("```\nline five\n```"));
+    }
+
+    #[test]
+    fn open_ended_ranges() {
+        let content = render_with(
+            &FILES,
+            &marker_block("<!-- snips: code.txt#L4- -->"),
+            &Config::default(),
+        )
+        .unwrap();
+        assert!(content.contains("```\n        line four\nline five\n```"));
+
+        let content = render_with(
+            &FILES,
+            &marker_block("<!-- snips: code.txt#L-L2 -->"),
+            &Config::default(),
+        )
+        .unwrap();
+        assert!(content.contains("```\nline one\n    line two\n```"));
+    }
+
+    #[test]
+    fn range_out_of_bounds() {
+        let dir = tempfile::tempdir().unwrap();
+        let code_path = dir.path().join("code.txt");
+
+        for range in ["L4-L9", "L3-L2", "L0-L1"] {
+            let markdown = marker_block(&format!("<!-- snips: code.txt#{range} -->"));
+            match render_in(dir.path(), &FILES, &markdown, &Config::default()) {
+                Err(SnipsError::InvalidLineRange {
+                    file,
+                    range: found,
+                    line_count,
+                }) => {
+                    assert_eq!(file, code_path);
+                    assert_eq!(found, range);
+                    assert_eq!(line_count, 5);
+                }
+                other => panic!("unexpected {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn drift_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let code_path = dir.path().join("code.txt");
+        fs::write(&code_path, SOURCE).unwrap();
+        let md_path = dir.path().join("doc.md");
+        write_marker(&md_path, "<!-- snips: code.txt#L2-L3 -->");
+        sync_snippets_in_file(&md_path, true).unwrap();
+
+        fs::write(&code_path, format!("inserted\n{SOURCE}")).unwrap();
+        let summary = sync_snippets_in_file_with_summary(&md_path, false).unwrap();
+        let report = &summary.snippets[0];
+        assert!(report.updated);
+        let warning = report.warning.as_deref().unwrap();
+        assert!(warning.contains("#L3-L4"), "{warning}");
+    }
+
+    #[test]
+    fn changed_content_without_drift_has_no_warning() {
+        let dir = tempfile::tempdir().unwrap();
+        let code_path = dir.path().join("code.txt");
+        fs::write(&code_path, SOURCE).unwrap();
+        let md_path = dir.path().join("doc.md");
+        write_marker(&md_path, "<!-- snips: code.txt#L2-L3 -->");
+        sync_snippets_in_file(&md_path, true).unwrap();
+
+        fs::write(&code_path, SOURCE.replace("two", "2")).unwrap();
+        let summary = sync_snippets_in_file_with_summary(&md_path, false).unwrap();
+        assert!(summary.snippets[0].updated);
+        assert!(summary.snippets[0].warning.is_none());
+    }
+}
@@ -0,0 +1,111 @@
+//! Integration tests for source marker validation.
+
+/// Verify `lint_source` reports malformed marker structure.
+#[cfg(test)]
+mod tests {
+    use snips::{Config, SnipsError, lint_source};
+    use std::fs;
+
+    fn lint(source: &str) -> Vec<SnipsError> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("code.rs");
+        fs::write(&path, source).unwrap();
+        lint_source(&path, &Config::default()).unwrap()
+    }
+
+    #[test]
+    fn well_formed_markers_pass() {
+        let source = "// snips-start: outer\n// snips-start: inner\n// snips-end: inner\n// snips-end\n// snips-start: other\n// snips-end: other\n";
+        assert!(lint(source).is_empty());
+    }
+
+    #[test]
+    fn repeated_names_are_parts() {
+        assert!(
+            lint("// snips-start: foo\n// snips-end\n\n// snips-start: foo\n// snips-end\n")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn reopened_names() {
+        let problems =
+            lint("// snips-start: foo\n\n\n// snips-start: foo\n// snips-end\n// snips-end\n");
+        match problems.as_slice() {
+            [
+                SnipsError::DuplicateSnippet {
+                    name,
+                    line,
+                    first_line,
+                    ..
+                },
+            ] => {
+                assert_eq!(name, "foo");
+                assert_eq!((*line, *first_line), (4, 1));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn end_without_start() {
+        let problems = lint("fn a() {}\n// snips-end: foo\n");
+        match problems.as_slice() {
+            [SnipsError::UnmatchedEndMarker { line, .. }] => assert_eq!(*line, 2),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn mismatched_names() {
+        let problems = lint("// snips-start: A\nfn a() {}\n// snips-end: B\n");
+        match problems.as_slice() {
+            [
+                SnipsError::MismatchedEndMarker {
+                    expected,
+                    found,
+                    line,
+                    ..
+                },
+            ] => {
+                assert_eq!((expected.as_str(), found.as_str(), *line), ("A", "B", 3));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn overlapping_regions() {
+        let problems =
+            lint("// snips-start: a\n// snips-start: b\n// snips-end: a\n// snips-end: b\n");
+        match problems.as_slice() {
+            [
+                SnipsError::OverlappingSnippets {
+                    name, inner, line, ..
+                },
+            ] => {
+                assert_eq!((name.as_str(), inner.as_str(), *line), ("a", "b", 3));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unterminated_start() {
+        let problems = lint("// snips-start: foo\nfn a() {}\n");
+        match problems.as_slice() {
+            [SnipsError::UnterminatedSnippet { name, line, .. }] => {
+                assert_eq!((name.as_str(), *line), ("foo", 1));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn binary_files_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("image.png");
+        fs::write(&path, [0xff, 0xfe, 0x00, 0x89]).unwrap();
+        assert!(lint_source(&path, &Config::default()).unwrap().is_empty());
+    }
+}
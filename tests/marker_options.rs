@@ -0,0 +1,121 @@
+//! Integration tests for key/value options on markdown markers.
+
+/// Verify each marker option and that options survive a rewrite.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, SnipsError, diff_file};
+    use std::fs;
+    use support::{marker_block, render_with};
+
+    const SOURCE: &str = "fn outer() {\n    // snips-start: body\n    let a = 1;\n    let b = 2;\n    let c = 3;\n    // snips-end: body\n}\n";
+    const FILES: [(&str, &str); 1] = [("code.rs", SOURCE)];
+
+    #[test]
+    fn lang_overrides_detection() {
+        let marker = "<!-- snips: code.rs#body lang=rs -->";
+        assert_eq!(
+            render_with(&FILES, &marker_block(marker), &Config::default()).unwrap(),
+            format!("{marker}\n```rs\nlet a = 1;\nlet b = 2;\nlet c = 3;\n```\n")
+        );
+    }
+
+    #[test]
+    fn lines_select_within_snippet() {
+        let marker = "<!-- snips: code.rs#body lines=2-3 -->";
+        assert_eq!(
+            render_with(&FILES, &marker_block(marker), &Config::default()).unwrap(),
+            format!("{marker}\n```rust\nlet b = 2;\nlet c = 3;\n```\n")
+        );
+        let marker = "<!-- snips: code.rs#body lines=2 -->";
+        assert_eq!(
+            render_with(&FILES, &marker_block(marker), &Config::default()).unwrap(),
+            format!("{marker}\n```rust\nlet b = 2;\n```\n")
+        );
+    }
+
+    #[test]
+    fn dedent_can_be_disabled() {
+        let marker = "<!-- snips: code.rs#body dedent=false -->";
+        assert_eq!(
+            render_with(&FILES, &marker_block(marker), &Config::default()).unwrap(),
+            format!("{marker}\n```rust\n    let a = 1;\n    let b = 2;\n    let c = 3;\n```\n")
+        );
+    }
+
+    #[test]
+    fn title_is_added_to_info_string() {
+        let marker = r#"<!-- snips: code.rs#body lines=1 title="My App" -->"#;
+        assert_eq!(
+            render_with(&FILES, &marker_block(marker), &Config::default()).unwrap(),
+            format!("{marker}\n```rust title=\"My App\"\nlet a = 1;\n```\n")
+        );
+    }
+
+    #[test]
+    fn strict_reports_whitespace_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("code.rs"), SOURCE).unwrap();
+        let md_path = dir.path().join("doc.md");
+        let body = "let a = 1;\nlet b = 2;\nlet c = 3;\n";
+        fs::write(
+            &md_path,
+            format!("<!-- snips: code.rs#body -->\n```rust\n{body}\n```\n"),
+        )
+        .unwrap();
+        assert!(diff_file(&md_path).unwrap().is_empty());
+        fs::write(
+            &md_path,
+            format!("<!-- snips: code.rs#body strict -->\n```rust\n{body}\n```\n"),
+        )
+        .unwrap();
+        assert!(!diff_file(&md_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn markers_are_written_back_as_written() {
+        // `render_with` also checks that a second run changes nothing.
+        for marker in [
+            "<!-- snips: code.rs#body strict=true   lines=1-1 -->",
+            "<!-- snips: code.rs#body dedent=true outputs=false -->",
+            r#"<!-- snips: code.rs#body title="App"  lang=rust -->"#,
+            r#"<!-- snips: code.rs#body replace="1 => one" shift=0 -->"#,
+        ] {
+            let content = render_with(&FILES, &marker_block(marker), &Config::default()).unwrap();
+            assert_eq!(content.lines().next(), Some(marker));
+        }
+    }
+
+    #[test]
+    fn unknown_option_is_an_error() {
+        let err = render_with(
+            &FILES,
+            "<!-- snips: code.rs#body colour=red -->\n```\n```\n",
+            &Config::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            &err,
+            SnipsError::InvalidMarkerOption { option, line: 1, .. } if option == "colour=red"
+        ));
+        assert!(
+            err.to_string()
+                .contains("lang, lines, dedent, title, strict")
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_errors() {
+        for option in ["lines=3-1", "lines=0", "dedent=maybe", "lang"] {
+            let markdown = format!("<!-- snips: code.rs#body {option} -->\n```\n```\n");
+            assert!(matches!(
+                render_with(&FILES, &markdown, &Config::default()),
+                Err(SnipsError::InvalidMarkerOption { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Integration tests for snippets made of several regions with one name.
+
+/// Verify repeated regions are joined in order.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, SnipsError};
+    use support::{fence_body, marker_block, render_with};
+
+    const SOURCE: &str = "\
+// snips-start: demo
+pub struct Point {
+    x: i32,
+}
+// snips-end: demo
+
+fn unrelated() {}
+
+impl Point {
+    fn helper() {}
+
+    // snips-start: demo
+    pub fn new(x: i32) -> Self {
+        Self { x }
+    }
+    // snips-end: demo
+}
+";
+    const FILES: [(&str, &str); 1] = [("point.rs", SOURCE)];
+
+    #[test]
+    fn parts_are_concatenated() {
+        let markdown = marker_block("<!-- snips: point.rs#demo -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert!(content.contains("```rust\n"));
+        assert_eq!(
+            fence_body(&content),
+            "pub struct Point {\n    x: i32,\n}\n    pub fn new(x: i32) -> Self {\n        Self { x }\n    }"
+        );
+    }
+
+    #[test]
+    fn parts_are_separated_by_elision() {
+        let config = Config {
+            elision: Some("// ...".to_string()),
+            ..Config::default()
+        };
+        let markdown = marker_block("<!-- snips: point.rs#demo -->");
+        let content = render_with(&FILES, &markdown, &config).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "pub struct Point {\n    x: i32,\n}\n    // ...\n    pub fn new(x: i32) -> Self {\n        Self { x }\n    }"
+        );
+    }
+
+    #[test]
+    fn unterminated_later_part() {
+        let source = "// snips-start: demo\nA\n// snips-end: demo\n// snips-start: demo\nB\n";
+        let markdown = marker_block("<!-- snips: point.rs#demo -->");
+        assert!(matches!(
+            render_with(&[("point.rs", source)], &markdown, &Config::default()),
+            Err(SnipsError::UnterminatedSnippet { name, line, .. }) if name == "demo" && line == 4
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+//! Integration tests for stripping nested snippet markers.
+
+/// Verify marker lines never leak into rendered snippets unless requested.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, lint_source};
+    use support::{fence_body, marker_block, render_in, render_with};
+
+    const SOURCE: &str = "fn main() {\n    // snips-start: outer\n    let a = 1;\n    // snips-start: inner\n    let b = 2;\n    // snips-end: inner\n    let c = 3;\n    // snips-end: outer\n}\n";
+    const FILES: [(&str, &str); 1] = [("code.rs", SOURCE)];
+
+    #[test]
+    fn nested_markers_are_stripped() {
+        let markdown = marker_block("<!-- snips: code.rs#outer -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert!(content.contains("```rust\n"));
+        assert_eq!(fence_body(&content), "let a = 1;\nlet b = 2;\nlet c = 3;");
+    }
+
+    #[test]
+    fn unnamed_end_closes_innermost_region() {
+        let source =
+            "// snips-start: a\nx();\n// snips-start: b\ny();\n// snips-end\nz();\n// snips-end\n";
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::default();
+        let files = [("code.rs", source)];
+        let markdown = marker_block("<!-- snips: code.rs#a -->");
+        let content = render_in(dir.path(), &files, &markdown, &config).unwrap();
+        assert_eq!(fence_body(&content), "x();\ny();\nz();");
+        let markdown = marker_block("<!-- snips: code.rs#b -->");
+        let content = render_in(dir.path(), &files, &markdown, &config).unwrap();
+        assert_eq!(fence_body(&content), "y();");
+        // Linting reads the markers the same way.
+        let problems = lint_source(&dir.path().join("code.rs"), &config).unwrap();
+        assert!(problems.is_empty(), "{problems:?}");
+    }
+
+    #[test]
+    fn whole_file_markers_are_stripped() {
+        let markdown = marker_block("<!-- snips: code.rs -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "fn main() {\n    let a = 1;\n    let b = 2;\n    let c = 3;\n}\n"
+        );
+    }
+
+    #[test]
+    fn markers_dedent_after_stripping() {
+        let files = [(
+            "code.rs",
+            "// snips-start: a\n    inner();\n// snips-end: a\n",
+        )];
+        let markdown = marker_block("<!-- snips: code.rs#L1-L3 -->");
+        let content = render_with(&files, &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "inner();");
+    }
+
+    #[test]
+    fn keep_markers_opt_in() {
+        let config = Config {
+            keep_markers: true,
+            ..Config::default()
+        };
+        let markdown = marker_block("<!-- snips: code.rs#outer -->");
+        let content = render_with(&FILES, &markdown, &config).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "let a = 1;\n// snips-start: inner\nlet b = 2;\n// snips-end: inner\nlet c = 3;"
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Integration tests for Jupyter notebooks as snippet sources.
+
+/// Verify notebook cells are selected by number, tag and name, with outputs.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, SnipsError};
+    use support::{fence_body, marker_block, render_with};
+
+    const NOTEBOOK: &str = r##"{
+ "cells": [
+  {
+   "cell_type": "markdown",
+   "metadata": {"tags": ["setup", "intro"]},
+   "source": ["# Tutorial\n", "\n", "Load the data."]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": 1,
+   "metadata": {"tags": ["setup"]},
+   "outputs": [],
+   "source": ["import json\n", "data = [1, 2, 3]"]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": 2,
+   "metadata": {},
+   "outputs": [
+    {"name": "stdout", "output_type": "stream", "text": ["total\n"]},
+    {
+     "data": {"text/plain": ["6"], "text/html": ["<b>6</b>"]},
+     "execution_count": 2,
+     "metadata": {},
+     "output_type": "execute_result"
+    }
+   ],
+   "source": [
+    "# snips-start: sum\n",
+    "print(\"total\")\n",
+    "sum(data)\n",
+    "# snips-end: sum"
+   ]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": 3,
+   "metadata": {"tags": ["setup", "teardown"]},
+   "outputs": [
+    {"ename": "ValueError", "evalue": "bad", "output_type": "error", "traceback": []}
+   ],
+   "source": "data.clear()\n"
+  }
+ ],
+ "metadata": {
+  "kernelspec": {"display_name": "Python 3", "language": "python", "name": "python3"},
+  "language_info": {"name": "python"}
+ },
+ "nbformat": 4,
+ "nbformat_minor": 5
+}
+"##;
+
+    const FILES: [(&str, &str); 1] = [("tutorial.ipynb", NOTEBOOK)];
+
+    #[test]
+    fn whole_notebook_is_its_code() {
+        let markdown = marker_block("<!-- snips: tutorial.ipynb -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(content.lines().nth(1), Some("```python"));
+        assert_eq!(
+            fence_body(&content),
+            "import json\ndata = [1, 2, 3]\n\nprint(\"total\")\nsum(data)\n\ndata.clear()"
+        );
+    }
+
+    #[test]
+    fn cells_by_number() {
+        let markdown = marker_block("<!-- snips: tutorial.ipynb#cell=1 -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "import json\ndata = [1, 2, 3]");
+        let markdown = marker_block("<!-- snips: tutorial.ipynb#cell=2-3 outputs -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "print(\"total\")\nsum(data)\n\ntotal\n\n6\n\ndata.clear()\n\nValueError: bad"
+        );
+        let markdown = marker_block("<!-- snips: tutorial.ipynb#cell=4 -->");
+        assert!(matches!(
+            render_with(&FILES, &markdown, &Config::default()),
+            Err(SnipsError::NotebookCellNotFound { cell_count: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn cells_by_tag() {
+        let markdown = marker_block("<!-- snips: tutorial.ipynb#tag=setup -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "import json\ndata = [1, 2, 3]\n\ndata.clear()"
+        );
+        let markdown = marker_block("<!-- snips: tutorial.ipynb#tag=intro -->");
+        assert!(matches!(
+            render_with(&FILES, &markdown, &Config::default()),
+            Err(SnipsError::NotebookTagNotFound { .. })
+        ));
+        let markdown = marker_block("<!-- snips: tutorial.ipynb#tag=plot -->");
+        match render_with(&FILES, &markdown, &Config::default()) {
+            Err(SnipsError::NotebookTagNotFound { available, .. }) => {
+                assert_eq!(available, "setup, teardown");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn named_regions_in_cells() {
+        let markdown = marker_block("<!-- snips: tutorial.ipynb#sum -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "print(\"total\")\nsum(data)");
+        let markdown = marker_block("<!-- snips: tutorial.ipynb#sum outputs -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "print(\"total\")\nsum(data)\n\ntotal\n\n6"
+        );
+    }
+
+    #[test]
+    fn outputs_need_notebook_cells() {
+        let markdown = marker_block("<!-- snips: tutorial.ipynb#L1-L2 outputs -->");
+        assert!(matches!(
+            render_with(&FILES, &markdown, &Config::default()),
+            Err(SnipsError::OutputsUnsupported { .. })
+        ));
+        let markdown = marker_block("<!-- snips: main.py#cell=1 -->");
+        assert!(matches!(
+            render_with(&[("main.py", "print(1)\n")], &markdown, &Config::default()),
+            Err(SnipsError::NotANotebook { .. })
+        ));
+    }
+}
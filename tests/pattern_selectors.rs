@@ -0,0 +1,76 @@
+//! Integration tests for regex-delimited snippet selection.
+
+/// Verify `#/start/../end/` selectors.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, SnipsError};
+    use support::{fence_body, marker_block, render_in, render_with};
+
+    const SOURCE: &str = "use std::fmt;\n\nimpl Parser {\n    pub fn parse(&self) {\n        todo!()\n    }\n}\n\npub fn other() {\n}\n";
+    const FILES: [(&str, &str); 1] = [("lib.rs", SOURCE)];
+
+    #[test]
+    fn selects_inclusive_region() {
+        let marker = marker_block("<!-- snips: lib.rs#/pub fn parse/../^    }/ -->");
+        let content = render_with(&FILES, &marker, &Config::default()).unwrap();
+        assert_eq!(
+            content,
+            "<!-- snips: lib.rs#/pub fn parse/../^    }/ -->\n```rust\npub fn parse(&self) {\n    todo!()\n}\n```\n"
+        );
+    }
+
+    #[test]
+    fn end_search_starts_after_start_line() {
+        let marker = marker_block("<!-- snips: lib.rs#/^pub fn other/../^}/ -->");
+        let content = render_with(&FILES, &marker, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "pub fn other() {\n}");
+    }
+
+    #[test]
+    fn reports_unmatched_start_pattern() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = marker_block("<!-- snips: lib.rs#/^fn missing/../^}/ -->");
+        match render_in(dir.path(), &FILES, &marker, &Config::default()) {
+            Err(SnipsError::PatternNotMatched {
+                file,
+                pattern,
+                start_line,
+            }) => {
+                assert_eq!(file, dir.path().join("lib.rs"));
+                assert_eq!(pattern, "^fn missing");
+                assert_eq!(start_line, 1);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reports_unmatched_end_pattern() {
+        let marker = marker_block("<!-- snips: lib.rs#/^impl Parser/../^END$/ -->");
+        match render_with(&FILES, &marker, &Config::default()) {
+            Err(SnipsError::PatternNotMatched {
+                pattern,
+                start_line,
+                ..
+            }) => {
+                assert_eq!(pattern, "^END$");
+                assert_eq!(start_line, 4);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reports_invalid_pattern() {
+        let marker = marker_block("<!-- snips: lib.rs#/(unclosed/../^}/ -->");
+        match render_with(&FILES, &marker, &Config::default()) {
+            Err(SnipsError::InvalidPattern { pattern, .. }) => assert_eq!(pattern, "(unclosed"),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+}
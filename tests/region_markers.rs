@@ -0,0 +1,104 @@
+//! Integration tests for region conventions from other documentation tools.
+
+/// Verify `#region`, `region`, AsciiDoc tag and mdBook anchor markers.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, RegionStyle, SnipsError};
+    use std::fs;
+    use support::{fence_body, marker_block, render_with};
+
+    #[test]
+    fn hash_region_directives_and_comments() {
+        for (file, source, expected) in [
+            (
+                "Program.cs",
+                "class A {\n    #region demo\n    int x;\n    #endregion\n}\n",
+                "int x;",
+            ),
+            (
+                "app.ts",
+                "//#region demo\nconst x = 1;\n// #endregion demo\n",
+                "const x = 1;",
+            ),
+        ] {
+            let markdown = marker_block(&format!("<!-- snips: {file}#demo -->"));
+            let content = render_with(&[(file, source)], &markdown, &Config::default()).unwrap();
+            assert_eq!(fence_body(&content), expected, "{file}");
+        }
+    }
+
+    #[test]
+    fn asciidoc_tags_and_mdbook_anchors() {
+        for (file, source, expected) in [
+            (
+                "main.rs",
+                "// tag::demo[]\nfn a() {}\n// end::demo[]\n",
+                "fn a() {}",
+            ),
+            (
+                "main.py",
+                "# ANCHOR: demo\nx = 1\n# ANCHOR_END: demo\n",
+                "x = 1",
+            ),
+        ] {
+            let markdown = marker_block(&format!("<!-- snips: {file}#demo -->"));
+            let content = render_with(&[(file, source)], &markdown, &Config::default()).unwrap();
+            assert_eq!(fence_body(&content), expected, "{file}");
+        }
+    }
+
+    #[test]
+    fn nested_region_markers_are_stripped() {
+        let source = "// ANCHOR: demo\nlet a = 1;\n// tag::inner[]\nlet b = 2;\n// end::inner[]\n// ANCHOR_END: demo\n";
+        let markdown = marker_block("<!-- snips: main.rs#demo -->");
+        let content = render_with(&[("main.rs", source)], &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "let a = 1;\nlet b = 2;");
+    }
+
+    #[test]
+    fn plain_region_is_opt_in() {
+        let files = [("main.java", "// region demo\nfn a() {}\n// endregion\n")];
+        let markdown = marker_block("<!-- snips: main.java#demo -->");
+        match render_with(&files, &markdown, &Config::default()) {
+            Err(SnipsError::SnippetNotFound { .. }) => {}
+            other => panic!("unexpected {other:?}"),
+        }
+        let config = Config {
+            regions: vec![RegionStyle::Region],
+            ..Config::default()
+        };
+        let content = render_with(&files, &markdown, &config).unwrap();
+        assert_eq!(fence_body(&content), "fn a() {}");
+    }
+
+    #[test]
+    fn disabled_styles_are_ignored() {
+        let config = Config {
+            regions: Vec::new(),
+            ..Config::default()
+        };
+        let files = [("main.rs", "// tag::demo[]\nfn a() {}\n// end::demo[]\n")];
+        let markdown = marker_block("<!-- snips: main.rs#demo -->");
+        match render_with(&files, &markdown, &config) {
+            Err(SnipsError::SnippetNotFound { .. }) => {}
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn regions_from_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snips.toml");
+        fs::write(&path, "regions = [\"region\", \"mdbook\"]\n").unwrap();
+        let config = Config::from_file(&path).unwrap();
+        assert_eq!(
+            config.regions,
+            vec![RegionStyle::Region, RegionStyle::Mdbook]
+        );
+    }
+}
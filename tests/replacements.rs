@@ -0,0 +1,82 @@
+//! Integration tests for substitution rules on rendered snippets.
+
+/// Verify config, marker and in-source replacement rules.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, Replacement, SnipsError};
+    use std::fs;
+    use support::{fence_body, marker_block, render_with};
+
+    const SOURCE: &str = "\
+// snips-replace: my_internal_crate::client:: =>
+// snips-start: demo
+let url = \"https://api.internal.corp/v1\";
+let key = std::env::var(\"API_KEY\").unwrap();
+let client = my_internal_crate::client::Client::new(url, key);
+// snips-end: demo
+";
+    const FILES: [(&str, &str); 1] = [("main.rs", SOURCE)];
+
+    #[test]
+    fn source_annotations_apply_to_the_file() {
+        let markdown = marker_block("<!-- snips: main.rs#demo -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "let url = \"https://api.internal.corp/v1\";\nlet key = std::env::var(\"API_KEY\").unwrap();\nlet client = Client::new(url, key);"
+        );
+    }
+
+    #[test]
+    fn config_and_marker_rules() {
+        let config = Config {
+            replace: vec![Replacement {
+                find: "https://api.internal.corp".to_string(),
+                with: "https://example.com".to_string(),
+                regex: false,
+            }],
+            ..Config::default()
+        };
+        let marker = r#"<!-- snips: main.rs#demo replace="/std::env::var\((\S+)\)\.unwrap\(\)/ => env($1)" -->"#;
+        let content = render_with(&FILES, &marker_block(marker), &config).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "let url = \"https://example.com/v1\";\nlet key = env(\"API_KEY\");\nlet client = Client::new(url, key);"
+        );
+    }
+
+    #[test]
+    fn rules_from_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snips.toml");
+        fs::write(
+            &path,
+            "[[replace]]\nfind = \"sk_\\\\w+\"\nwith = \"sk_...\"\nregex = true\n",
+        )
+        .unwrap();
+        let config = Config::from_file(&path).unwrap();
+        assert_eq!(
+            config.replace[0].apply("key = sk_abc123").unwrap(),
+            "key = sk_..."
+        );
+    }
+
+    #[test]
+    fn malformed_rules_are_errors() {
+        let markdown = marker_block("<!-- snips: main.rs#demo replace=oops -->");
+        assert!(matches!(
+            render_with(&FILES, &markdown, &Config::default()),
+            Err(SnipsError::InvalidMarkerOption { .. })
+        ));
+        let markdown = marker_block(r#"<!-- snips: main.rs#demo replace="/(/ => x" -->"#);
+        assert!(matches!(
+            render_with(&FILES, &markdown, &Config::default()),
+            Err(SnipsError::InvalidPattern { .. })
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+//! Integration tests for parser-backed Rust item extraction.
+
+/// Verify `item=`, `fn=` and `sig=` selectors.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, SnipsError};
+    use support::{fence_body, marker_block, render_in, render_with};
+
+    const SOURCE: &str = r#"//! Crate docs.
+
+/// A parser.
+#[derive(Debug)]
+pub struct Parser {
+    input: String,
+}
+
+impl Parser {
+    /// Parse the input.
+    pub fn parse(&self) -> usize {
+        let n = self.input.len();
+        n * 2
+    }
+
+    fn helper() {}
+}
+
+impl std::fmt::Display for Parser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.input)
+    }
+}
+
+mod inner {
+    pub fn nested() -> u8 {
+        1
+    }
+}
+"#;
+    const FILES: [(&str, &str); 1] = [("lib.rs", SOURCE)];
+
+    #[test]
+    fn struct_with_attributes_and_docs() {
+        let markdown = marker_block("<!-- snips: lib.rs#item=Parser -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert!(content.contains("```rust\n"));
+        assert_eq!(
+            fence_body(&content),
+            "/// A parser.\n#[derive(Debug)]\npub struct Parser {\n    input: String,\n}"
+        );
+    }
+
+    #[test]
+    fn method_by_path_is_dedented() {
+        let markdown = marker_block("<!-- snips: lib.rs#item=Parser::parse -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "/// Parse the input.\npub fn parse(&self) -> usize {\n    let n = self.input.len();\n    n * 2\n}"
+        );
+    }
+
+    #[test]
+    fn function_by_name() {
+        let markdown = marker_block("<!-- snips: lib.rs#fn=nested -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "pub fn nested() -> u8 {\n    1\n}");
+    }
+
+    #[test]
+    fn impl_blocks_with_elided_bodies() {
+        let markdown = marker_block("<!-- snips: lib.rs#sig=impl-Parser -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "impl Parser {\n    /// Parse the input.\n    pub fn parse(&self) -> usize { ... }\n\n    fn helper() { ... }\n}"
+        );
+        let markdown = marker_block("<!-- snips: lib.rs#item=impl-Display-for-Parser -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert!(
+            fence_body(&content).starts_with("impl std::fmt::Display for Parser {\n    fn fmt")
+        );
+    }
+
+    #[test]
+    fn missing_item_lists_available() {
+        let dir = tempfile::tempdir().unwrap();
+        let markdown = marker_block("<!-- snips: lib.rs#item=Parser::missing -->");
+        match render_in(dir.path(), &FILES, &markdown, &Config::default()) {
+            Err(SnipsError::ItemNotFound {
+                file,
+                item,
+                available_items,
+            }) => {
+                assert_eq!(file, dir.path().join("lib.rs"));
+                assert_eq!(item, "Parser::missing");
+                assert_eq!(
+                    available_items,
+                    "Parser, impl-Parser, Parser::parse, Parser::helper, impl-Display-for-Parser, Parser::fmt, inner, inner::nested"
+                );
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unparsable_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let markdown = marker_block("<!-- snips: lib.rs#fn=broken -->");
+        let files = [("lib.rs", "fn broken( {\n")];
+        match render_in(dir.path(), &files, &markdown, &Config::default()) {
+            Err(SnipsError::SourceParseFailed { file, .. }) => {
+                assert_eq!(file, dir.path().join("lib.rs"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_selector_kind() {
+        let markdown = marker_block("<!-- snips: lib.rs#nope=Parser -->");
+        match render_with(&FILES, &markdown, &Config::default()) {
+            Err(SnipsError::UnknownSelector { selector, .. }) => {
+                assert_eq!(selector, "nope=Parser");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+}
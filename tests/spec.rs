@@ -50,6 +50,11 @@ mod tests {
             }
             other => panic!("unexpected {other:?}"),
         }
+        let message = sync_snippets_in_file(&md_path, false)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("Selectors: name, L10-L20"));
+        assert!(message.contains("<!-- snips: $ command -->"));
     }
 
     #[test]
@@ -69,9 +74,10 @@ mod tests {
         let md_path = dir.path().join("doc.md");
         write_marker(&md_path, "<!-- snips: code.rs#foo -->");
         match sync_snippets_in_file(&md_path, false) {
-            Err(SnipsError::UnterminatedSnippet(p, name)) => {
-                assert_eq!(p, code_path);
+            Err(SnipsError::UnterminatedSnippet { file, name, line }) => {
+                assert_eq!(file, code_path);
                 assert_eq!(name, "foo");
+                assert_eq!(line, 1);
             }
             other => panic!("unexpected {other:?}"),
         }
@@ -85,8 +91,8 @@ mod tests {
         let md_path = dir.path().join("doc.md");
         write_marker(&md_path, "<!-- snips: code.rs#A -->");
         match sync_snippets_in_file(&md_path, false) {
-            Err(SnipsError::UnterminatedSnippet(p, name)) => {
-                assert_eq!(p, code_path);
+            Err(SnipsError::UnterminatedSnippet { file, name, .. }) => {
+                assert_eq!(file, code_path);
                 assert_eq!(name, "A".to_string());
             }
             other => panic!("unexpected {other:?}"),
@@ -0,0 +1,174 @@
+//! Integration tests for JSON, TOML and YAML subtree selectors.
+
+/// Verify subtrees of data files are extracted with their formatting intact.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, SnipsError};
+    use support::{fence_body, marker_block, render_with};
+
+    const CARGO: &str = r#"[package]
+name = "demo"
+version = "0.1.0"
+
+# Runtime dependencies.
+[dependencies]
+serde = { version = "1", features = ["derive"] } # derive support
+regex = "1"
+
+[dependencies.toml]
+version = "0.8"
+
+# Test-only dependencies.
+[dev-dependencies]
+tempfile = "3"
+
+[[bin]]
+name = "one"
+
+[[bin]]
+name = "two"
+"#;
+
+    const COMPOSE: &str = "\
+version: '3'
+services:
+  # The web front end.
+  web:
+    image: nginx
+    ports:
+      - \"80:80\"   # public
+  db:
+    image: postgres
+";
+
+    const SCHEMA: &str = r#"{
+  "type": "object",
+  "properties": {
+    "server": {
+      "type": "object",
+      "properties": { "port": { "type": "integer" } }
+    },
+    "a/b": [1, 2, 3]
+  }
+}
+"#;
+
+    const FILES: [(&str, &str); 3] = [
+        ("Cargo.toml", CARGO),
+        ("compose.yml", COMPOSE),
+        ("schema.json", SCHEMA),
+    ];
+
+    #[test]
+    fn toml_tables() {
+        let markdown = marker_block("<!-- snips: Cargo.toml#toml=dependencies -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "# Runtime dependencies.\n[dependencies]\nserde = { version = \"1\", features = [\"derive\"] } # derive support\nregex = \"1\"\n\n[dependencies.toml]\nversion = \"0.8\""
+        );
+        let markdown = marker_block("<!-- snips: Cargo.toml#toml=bin -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "[[bin]]\nname = \"one\"\n\n[[bin]]\nname = \"two\""
+        );
+    }
+
+    #[test]
+    fn interleaved_toml_tables() {
+        let manifest = "\
+[dependencies]
+serde = \"1\"
+
+[features]
+default = []
+
+# Async runtime.
+[dependencies.tokio]
+version = \"1\"
+";
+        let markdown = "<!-- snips: Cargo.toml#toml=dependencies -->\n```\n```\n";
+        let content =
+            render_with(&[("Cargo.toml", manifest)], markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "[dependencies]\nserde = \"1\"\n\n# Async runtime.\n[dependencies.tokio]\nversion = \"1\""
+        );
+    }
+
+    #[test]
+    fn toml_keys() {
+        let markdown = marker_block("<!-- snips: Cargo.toml#toml=dependencies.regex -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "regex = \"1\"");
+        let markdown = marker_block("<!-- snips: Cargo.toml#toml=package.version -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "version = \"0.1.0\"");
+    }
+
+    #[test]
+    fn yaml_keys() {
+        let markdown = marker_block("<!-- snips: compose.yml#yaml=services.web -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "# The web front end.\nweb:\n  image: nginx\n  ports:\n    - \"80:80\"   # public"
+        );
+        let markdown = marker_block("<!-- snips: compose.yml#yaml=services.web.ports.0 -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "- \"80:80\"   # public");
+    }
+
+    #[test]
+    fn json_pointers() {
+        let markdown = marker_block("<!-- snips: schema.json#json=/properties/server -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "{\n  \"type\": \"object\",\n  \"properties\": { \"port\": { \"type\": \"integer\" } }\n}"
+        );
+        let markdown = marker_block("<!-- snips: schema.json#json=/properties/a~1b/2 -->");
+        let content = render_with(&FILES, &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "3");
+    }
+
+    #[test]
+    fn missing_paths_list_alternatives() {
+        let markdown = marker_block("<!-- snips: schema.json#json=/properties/client -->");
+        match render_with(&FILES, &markdown, &Config::default()) {
+            Err(SnipsError::ItemNotFound {
+                available_items, ..
+            }) => assert_eq!(available_items, "/properties/server, /properties/a/b"),
+            other => panic!("unexpected {other:?}"),
+        }
+        let markdown = marker_block("<!-- snips: compose.yml#yaml=services.cache -->");
+        match render_with(&FILES, &markdown, &Config::default()) {
+            Err(SnipsError::ItemNotFound {
+                available_items, ..
+            }) => assert_eq!(available_items, "services.web, services.db"),
+            other => panic!("unexpected {other:?}"),
+        }
+        let markdown = marker_block("<!-- snips: Cargo.toml#toml=workspace -->");
+        assert!(matches!(
+            render_with(&FILES, &markdown, &Config::default()),
+            Err(SnipsError::ItemNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn json_pointers_must_start_with_a_slash() {
+        let markdown = marker_block("<!-- snips: schema.json#json=properties/server -->");
+        match render_with(&FILES, &markdown, &Config::default()) {
+            Err(SnipsError::InvalidJsonPointer { pointer, .. }) => {
+                assert_eq!(pointer, "properties/server");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use std::fs::File;
+use snips::{Config, SnipsError, sync_snippets_with_config};
+use std::fs::{self, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
@@ -55,3 +56,53 @@ pub fn make_example(dir: &TempDir) -> PathBuf {
     write_marker(&md, "<!-- snips: code.rs -->");
     md
 }
+
+/// A marker followed by a backtick fence holding "old".
+pub fn marker_block(marker: &str) -> String {
+    format!("{marker}\n```\nold\n```\n")
+}
+
+/// Write `files` into `dir`, render `markdown` as `README.md` beside them
+/// using `config`, and return the rendered markdown.
+///
+/// Rendering is run twice and the second run must leave the file unchanged.
+pub fn render_in(
+    dir: &Path,
+    files: &[(&str, &str)],
+    markdown: &str,
+    config: &Config,
+) -> Result<String, SnipsError> {
+    for (name, content) in files {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(path, content).unwrap();
+    }
+    let md_path = dir.join("README.md");
+    fs::write(&md_path, markdown).unwrap();
+    sync_snippets_with_config(&md_path, true, config)?;
+    let rendered = fs::read_to_string(&md_path).unwrap();
+    sync_snippets_with_config(&md_path, true, config)?;
+    assert_eq!(fs::read_to_string(&md_path).unwrap(), rendered);
+    Ok(rendered)
+}
+
+/// Like [`render_in`], in a fresh temporary directory.
+pub fn render_with(
+    files: &[(&str, &str)],
+    markdown: &str,
+    config: &Config,
+) -> Result<String, SnipsError> {
+    let dir = tempfile::tempdir().unwrap();
+    render_in(dir.path(), files, markdown, config)
+}
+
+/// The body of the backtick fence in rendered `markdown`, without its
+/// trailing newline.
+pub fn fence_body(markdown: &str) -> &str {
+    let body = markdown.split_once("```").unwrap().1;
+    let body = body.split_once('\n').unwrap().1;
+    let body = body.rsplit_once("```").unwrap().0;
+    body.strip_suffix('\n').unwrap_or(body)
+}
@@ -0,0 +1,192 @@
+//! Integration tests for language-aware symbol extraction.
+
+/// Verify `#symbol=` selectors across supported languages.
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code, missing_docs)]
+    mod support {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/support/mod.rs"));
+    }
+
+    use snips::{Config, SnipsError};
+    use support::{fence_body, marker_block, render_in, render_with};
+
+    const PYTHON: &str = r#"import os
+
+
+class Greeter:
+    """Says hello."""
+
+    # Build a greeting.
+    @staticmethod
+    def greet(name):
+        return f"hello {name}"
+
+    def other(self):
+        pass
+
+
+def main():
+    print(Greeter.greet("you"))
+"#;
+
+    #[test]
+    fn python_method_with_decorator_and_comment() {
+        let markdown = marker_block("<!-- snips: app.py#symbol=Greeter.greet -->");
+        let content = render_with(&[("app.py", PYTHON)], &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "# Build a greeting.\n@staticmethod\ndef greet(name):\n    return f\"hello {name}\""
+        );
+    }
+
+    #[test]
+    fn python_function() {
+        let markdown = marker_block("<!-- snips: app.py#symbol=main -->");
+        let content = render_with(&[("app.py", PYTHON)], &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "def main():\n    print(Greeter.greet(\"you\"))"
+        );
+    }
+
+    const TYPESCRIPT: &str = r#"// A counter.
+export class Counter {
+  private count = 0;
+
+  increment(by: number): number {
+    this.count += by;
+    return this.count;
+  }
+}
+
+export const double = (n: number): number => n * 2;
+
+interface Shape {
+  area(): number;
+}
+"#;
+
+    #[test]
+    fn typescript_class_method_and_arrow_function() {
+        let markdown = marker_block("<!-- snips: counter.ts#symbol=Counter.increment -->");
+        let content =
+            render_with(&[("counter.ts", TYPESCRIPT)], &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "increment(by: number): number {\n  this.count += by;\n  return this.count;\n}"
+        );
+        let markdown = marker_block("<!-- snips: counter.ts#symbol=double -->");
+        let content =
+            render_with(&[("counter.ts", TYPESCRIPT)], &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "export const double = (n: number): number => n * 2;"
+        );
+        let markdown = marker_block("<!-- snips: counter.ts#symbol=Counter -->");
+        let content =
+            render_with(&[("counter.ts", TYPESCRIPT)], &markdown, &Config::default()).unwrap();
+        assert!(fence_body(&content).starts_with("// A counter.\nexport class"));
+        let markdown = marker_block("<!-- snips: counter.ts#symbol=Shape -->");
+        let content =
+            render_with(&[("counter.ts", TYPESCRIPT)], &markdown, &Config::default()).unwrap();
+        assert!(fence_body(&content).starts_with("interface Shape {"));
+    }
+
+    const GO: &str = r#"package main
+
+// Server handles requests.
+type Server struct {
+	addr string
+}
+
+// Start launches the server.
+func (s *Server) Start() error {
+	return nil
+}
+
+// Push adds a value.
+func (s *Stack[T]) Push(v T) {}
+
+func (q Queue[K, V]) Len() int {
+	return 0
+}
+
+func main() {}
+"#;
+
+    #[test]
+    fn go_type_and_method() {
+        let markdown = marker_block("<!-- snips: main.go#symbol=Server -->");
+        let content = render_with(&[("main.go", GO)], &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "// Server handles requests.\ntype Server struct {\n\taddr string\n}"
+        );
+        let markdown = marker_block("<!-- snips: main.go#symbol=Server.Start -->");
+        let content = render_with(&[("main.go", GO)], &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "// Start launches the server.\nfunc (s *Server) Start() error {\n\treturn nil\n}"
+        );
+    }
+
+    #[test]
+    fn go_generic_receivers() {
+        let markdown = marker_block("<!-- snips: main.go#symbol=Stack.Push -->");
+        let content = render_with(&[("main.go", GO)], &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "// Push adds a value.\nfunc (s *Stack[T]) Push(v T) {}"
+        );
+        let markdown = marker_block("<!-- snips: main.go#symbol=Queue.Len -->");
+        let content = render_with(&[("main.go", GO)], &markdown, &Config::default()).unwrap();
+        assert_eq!(
+            fence_body(&content),
+            "func (q Queue[K, V]) Len() int {\n\treturn 0\n}"
+        );
+    }
+
+    #[test]
+    fn rust_symbols_use_item_paths() {
+        let source = "struct A;\n\nimpl A {\n    fn b(&self) {}\n}\n";
+        let markdown = marker_block("<!-- snips: lib.rs#symbol=A.b -->");
+        let content = render_with(&[("lib.rs", source)], &markdown, &Config::default()).unwrap();
+        assert_eq!(fence_body(&content), "fn b(&self) {}");
+    }
+
+    #[test]
+    fn missing_symbol_lists_available() {
+        let dir = tempfile::tempdir().unwrap();
+        let markdown = marker_block("<!-- snips: app.py#symbol=Greeter.missing -->");
+        let files = [("app.py", PYTHON)];
+        match render_in(dir.path(), &files, &markdown, &Config::default()) {
+            Err(SnipsError::ItemNotFound {
+                item,
+                available_items,
+                ..
+            }) => {
+                assert_eq!(item, "Greeter.missing");
+                assert_eq!(
+                    available_items,
+                    "Greeter, Greeter.greet, Greeter.other, main"
+                );
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unsupported_language() {
+        let dir = tempfile::tempdir().unwrap();
+        let markdown = marker_block("<!-- snips: script.rb#symbol=hello -->");
+        let files = [("script.rb", "def hello\nend\n")];
+        match render_in(dir.path(), &files, &markdown, &Config::default()) {
+            Err(SnipsError::UnsupportedLanguage { file, language, .. }) => {
+                assert_eq!(file, dir.path().join("script.rb"));
+                assert_eq!(language, "Ruby");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+}